@@ -0,0 +1,40 @@
+//! Administration of the Portkey organisation.
+//!
+//! Admin APIs require a Portkey API key with admin permissions. The client
+//! does not need provider credentials:
+//!
+//! ```rust
+//! let client = portkey::Client::builder()
+//!     .api_key("your-portkey-admin-api-key")
+//!     .build()
+//!     .expect("valid Portkey configuration");
+//!
+//! let virtual_keys = client.admin().virtual_keys();
+//! ```
+
+mod virtual_keys;
+
+pub use virtual_keys::{
+    CreateVirtualKeyRequest, RateLimit, RateLimitKind, RateLimitUnit, ResetPeriod,
+    UpdateVirtualKeyRequest, UsageLimits, VirtualKey, VirtualKeyCreated, VirtualKeyList,
+    VirtualKeyProvider, VirtualKeys,
+};
+
+use crate::Client;
+
+/// Admin APIs.
+#[derive(Debug, Clone, Copy)]
+pub struct Admin<'c> {
+    client: &'c Client,
+}
+
+impl<'c> Admin<'c> {
+    pub(crate) fn new(client: &'c Client) -> Self {
+        Self { client }
+    }
+
+    /// Returns the virtual keys API.
+    pub fn virtual_keys(&self) -> VirtualKeys<'c> {
+        VirtualKeys::new(self.client)
+    }
+}
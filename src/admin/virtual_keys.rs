@@ -0,0 +1,629 @@
+//! Virtual keys holding provider credentials in Portkey.
+
+use std::fmt;
+
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{http::path, Client, PortkeyResponse, ResponseMeta, Result};
+
+/// Virtual keys API.
+///
+/// Virtual keys store provider credentials in Portkey and are referenced by
+/// their slug, e.g. with [`Auth::VirtualKey`](crate::Auth::VirtualKey).
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use portkey::admin::{
+///     CreateVirtualKeyRequest, RateLimit, RateLimitUnit, ResetPeriod, UsageLimits,
+///     VirtualKeyProvider,
+/// };
+/// use portkey::Client;
+///
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::builder()
+///     .api_key("your-portkey-admin-api-key")
+///     .build()?;
+///
+/// let request = CreateVirtualKeyRequest::new("tenant-42", VirtualKeyProvider::OpenAI, "sk-...")
+///     .usage_limits(UsageLimits::new(100.0).periodic_reset(ResetPeriod::Monthly))
+///     .rate_limit(RateLimit::requests(600, RateLimitUnit::PerMinute));
+///
+/// let created = client.admin().virtual_keys().create(&request).await?;
+/// println!("created virtual key {}", created.slug);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct VirtualKeys<'c> {
+    client: &'c Client,
+}
+
+impl<'c> VirtualKeys<'c> {
+    pub(crate) fn new(client: &'c Client) -> Self {
+        Self { client }
+    }
+
+    /// Creates a virtual key.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Api`](crate::Error::Api) if the gateway returns an
+    /// error response, e.g. when the API key lacks admin permissions, and
+    /// [`Error::Request`](crate::Error::Request) if the request fails.
+    pub async fn create(
+        &self,
+        request: &CreateVirtualKeyRequest,
+    ) -> Result<PortkeyResponse<VirtualKeyCreated>> {
+        let response: PortkeyResponse<Created> = self.client.post("/virtual-keys", request).await?;
+        let (created, meta) = response.into_parts();
+        Ok(PortkeyResponse::new(created.data, meta))
+    }
+
+    /// Lists the virtual keys of the workspace.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`VirtualKeys::create`].
+    pub async fn list(&self) -> Result<PortkeyResponse<VirtualKeyList>> {
+        self.client.get("/virtual-keys").await
+    }
+
+    /// Retrieves the virtual key `slug`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`](crate::Error::InvalidArgument) if
+    /// `slug` is empty, `.` or `..`, otherwise same as
+    /// [`VirtualKeys::create`].
+    pub async fn retrieve(&self, slug: &str) -> Result<PortkeyResponse<VirtualKey>> {
+        self.client.get(&path(&["virtual-keys", slug])?).await
+    }
+
+    /// Updates the virtual key `slug`. Settings not set in `request` are kept.
+    /// Returns Portkey's response metadata.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`VirtualKeys::retrieve`].
+    pub async fn update(
+        &self,
+        slug: &str,
+        request: &UpdateVirtualKeyRequest,
+    ) -> Result<ResponseMeta> {
+        self.client
+            .send_discarding(Method::PUT, &path(&["virtual-keys", slug])?, Some(request))
+            .await
+    }
+
+    /// Deletes the virtual key `slug`, returning Portkey's response metadata.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`VirtualKeys::retrieve`].
+    pub async fn delete(&self, slug: &str) -> Result<ResponseMeta> {
+        self.client
+            .send_discarding(Method::DELETE, &path(&["virtual-keys", slug])?, None::<&()>)
+            .await
+    }
+}
+
+/// Response envelope of the create endpoint.
+#[derive(Deserialize)]
+struct Created {
+    data: VirtualKeyCreated,
+}
+
+/// Request to create a virtual key.
+#[derive(Clone, PartialEq, Serialize)]
+pub struct CreateVirtualKeyRequest {
+    /// Display name of the virtual key.
+    name: String,
+    /// Provider the key belongs to.
+    provider: VirtualKeyProvider,
+    /// API key of the provider.
+    key: String,
+    /// Free-form note shown in Portkey.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    note: Option<String>,
+    /// Name of the Azure OpenAI resource.
+    #[serde(rename = "resourceName", skip_serializing_if = "Option::is_none")]
+    resource_name: Option<String>,
+    /// Name of the Azure OpenAI deployment.
+    #[serde(rename = "deploymentName", skip_serializing_if = "Option::is_none")]
+    deployment_name: Option<String>,
+    /// Azure OpenAI API version.
+    #[serde(rename = "apiVersion", skip_serializing_if = "Option::is_none")]
+    api_version: Option<String>,
+    /// Workspace the key is created in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    workspace_id: Option<String>,
+    /// Budget of the key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    usage_limits: Option<UsageLimits>,
+    /// Rate limits of the key.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    rate_limits: Vec<RateLimit>,
+}
+
+impl fmt::Debug for CreateVirtualKeyRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CreateVirtualKeyRequest")
+            .field("name", &self.name)
+            .field("provider", &self.provider)
+            .field("key", &"<redacted>")
+            .field("note", &self.note)
+            .field("resource_name", &self.resource_name)
+            .field("deployment_name", &self.deployment_name)
+            .field("api_version", &self.api_version)
+            .field("workspace_id", &self.workspace_id)
+            .field("usage_limits", &self.usage_limits)
+            .field("rate_limits", &self.rate_limits)
+            .finish()
+    }
+}
+
+impl CreateVirtualKeyRequest {
+    /// Creates a request for a virtual key named `name` storing the provider
+    /// API key `key`.
+    pub fn new(
+        name: impl Into<String>,
+        provider: VirtualKeyProvider,
+        key: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            provider,
+            key: key.into(),
+            note: None,
+            resource_name: None,
+            deployment_name: None,
+            api_version: None,
+            workspace_id: None,
+            usage_limits: None,
+            rate_limits: Vec::new(),
+        }
+    }
+
+    /// Sets a free-form note shown in Portkey.
+    pub fn note(mut self, note: impl Into<String>) -> Self {
+        self.note = Some(note.into());
+        self
+    }
+
+    /// Sets the deployment of a [`VirtualKeyProvider::AzureOpenAI`] key.
+    pub fn azure_deployment(
+        mut self,
+        resource_name: impl Into<String>,
+        deployment_name: impl Into<String>,
+        api_version: impl Into<String>,
+    ) -> Self {
+        self.resource_name = Some(resource_name.into());
+        self.deployment_name = Some(deployment_name.into());
+        self.api_version = Some(api_version.into());
+        self
+    }
+
+    /// Creates the key in workspace `workspace_id` instead of the API key's
+    /// default workspace.
+    pub fn workspace_id(mut self, workspace_id: impl Into<String>) -> Self {
+        self.workspace_id = Some(workspace_id.into());
+        self
+    }
+
+    /// Sets the budget of the key.
+    pub fn usage_limits(mut self, usage_limits: UsageLimits) -> Self {
+        self.usage_limits = Some(usage_limits);
+        self
+    }
+
+    /// Adds a rate limit.
+    pub fn rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limits.push(rate_limit);
+        self
+    }
+}
+
+/// Request to update a virtual key.
+#[derive(Clone, Default, PartialEq, Serialize)]
+pub struct UpdateVirtualKeyRequest {
+    /// Display name of the virtual key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    /// API key of the provider.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    key: Option<String>,
+    /// Free-form note shown in Portkey.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    note: Option<String>,
+    /// Budget of the key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    usage_limits: Option<UsageLimits>,
+    /// Rate limits of the key, replacing the current ones.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rate_limits: Option<Vec<RateLimit>>,
+}
+
+impl fmt::Debug for UpdateVirtualKeyRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UpdateVirtualKeyRequest")
+            .field("name", &self.name)
+            .field("key", &self.key.as_ref().map(|_| "<redacted>"))
+            .field("note", &self.note)
+            .field("usage_limits", &self.usage_limits)
+            .field("rate_limits", &self.rate_limits)
+            .finish()
+    }
+}
+
+impl UpdateVirtualKeyRequest {
+    /// Creates a request that changes nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Renames the key.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Replaces the stored provider API key, e.g. after a rotation.
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Sets a free-form note shown in Portkey.
+    pub fn note(mut self, note: impl Into<String>) -> Self {
+        self.note = Some(note.into());
+        self
+    }
+
+    /// Replaces the budget of the key.
+    pub fn usage_limits(mut self, usage_limits: UsageLimits) -> Self {
+        self.usage_limits = Some(usage_limits);
+        self
+    }
+
+    /// Replaces the rate limits of the key. An empty list removes them.
+    pub fn rate_limits(mut self, rate_limits: impl IntoIterator<Item = RateLimit>) -> Self {
+        self.rate_limits = Some(rate_limits.into_iter().collect());
+        self
+    }
+}
+
+/// Provider of a virtual key.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+#[non_exhaustive]
+pub enum VirtualKeyProvider {
+    /// OpenAI, `openai`.
+    OpenAI,
+    /// Anthropic, `anthropic`.
+    Anthropic,
+    /// Azure OpenAI Service, `azure-openai`.
+    AzureOpenAI,
+    /// AWS Bedrock, `bedrock`.
+    Bedrock,
+    /// Google Vertex AI, `vertex-ai`.
+    VertexAi,
+    /// Google Gemini, `google`.
+    Google,
+    /// Mistral AI, `mistral-ai`.
+    MistralAi,
+    /// Cohere, `cohere`.
+    Cohere,
+    /// Groq, `groq`.
+    Groq,
+    /// Any other provider, by its Portkey slug.
+    Other(String),
+}
+
+impl VirtualKeyProvider {
+    /// Returns the Portkey slug of the provider.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::OpenAI => "openai",
+            Self::Anthropic => "anthropic",
+            Self::AzureOpenAI => "azure-openai",
+            Self::Bedrock => "bedrock",
+            Self::VertexAi => "vertex-ai",
+            Self::Google => "google",
+            Self::MistralAi => "mistral-ai",
+            Self::Cohere => "cohere",
+            Self::Groq => "groq",
+            Self::Other(provider) => provider,
+        }
+    }
+}
+
+impl fmt::Display for VirtualKeyProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<String> for VirtualKeyProvider {
+    fn from(provider: String) -> Self {
+        match provider.as_str() {
+            "openai" => Self::OpenAI,
+            "anthropic" => Self::Anthropic,
+            "azure-openai" => Self::AzureOpenAI,
+            "bedrock" => Self::Bedrock,
+            "vertex-ai" => Self::VertexAi,
+            "google" => Self::Google,
+            "mistral-ai" => Self::MistralAi,
+            "cohere" => Self::Cohere,
+            "groq" => Self::Groq,
+            _ => Self::Other(provider),
+        }
+    }
+}
+
+impl From<VirtualKeyProvider> for String {
+    fn from(provider: VirtualKeyProvider) -> Self {
+        match provider {
+            VirtualKeyProvider::Other(provider) => provider,
+            provider => provider.as_str().to_string(),
+        }
+    }
+}
+
+/// Budget of a virtual key.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct UsageLimits {
+    /// Spending limit in USD, after which requests are rejected.
+    pub credit_limit: f64,
+    /// Spending in USD at which an alert is sent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alert_threshold: Option<f64>,
+    /// Period after which spending is reset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub periodic_reset: Option<ResetPeriod>,
+}
+
+impl UsageLimits {
+    /// Creates a budget of `credit_limit` USD that is never reset.
+    pub fn new(credit_limit: f64) -> Self {
+        Self {
+            credit_limit,
+            alert_threshold: None,
+            periodic_reset: None,
+        }
+    }
+
+    /// Sends an alert once spending reaches `alert_threshold` USD.
+    pub fn alert_threshold(mut self, alert_threshold: f64) -> Self {
+        self.alert_threshold = Some(alert_threshold);
+        self
+    }
+
+    /// Resets spending every `period`.
+    pub fn periodic_reset(mut self, period: ResetPeriod) -> Self {
+        self.periodic_reset = Some(period);
+        self
+    }
+}
+
+/// Period after which the spending of a [`UsageLimits`] budget is reset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum ResetPeriod {
+    /// At the start of every week.
+    Weekly,
+    /// At the start of every month.
+    Monthly,
+}
+
+/// Rate limit of a virtual key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RateLimit {
+    /// What is limited.
+    #[serde(rename = "type")]
+    pub kind: RateLimitKind,
+    /// Period the limit applies to.
+    pub unit: RateLimitUnit,
+    /// Maximum number of requests or tokens per period.
+    pub value: u64,
+}
+
+impl RateLimit {
+    /// Limits the number of requests per `unit`.
+    pub fn requests(value: u64, unit: RateLimitUnit) -> Self {
+        Self {
+            kind: RateLimitKind::Requests,
+            unit,
+            value,
+        }
+    }
+
+    /// Limits the number of tokens per `unit`.
+    pub fn tokens(value: u64, unit: RateLimitUnit) -> Self {
+        Self {
+            kind: RateLimitKind::Tokens,
+            unit,
+            value,
+        }
+    }
+}
+
+/// What a [`RateLimit`] limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum RateLimitKind {
+    /// Number of requests.
+    Requests,
+    /// Number of tokens.
+    Tokens,
+}
+
+/// Period a [`RateLimit`] applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub enum RateLimitUnit {
+    /// Per minute, `rpm`.
+    #[serde(rename = "rpm")]
+    PerMinute,
+    /// Per hour, `rph`.
+    #[serde(rename = "rph")]
+    PerHour,
+    /// Per day, `rpd`.
+    #[serde(rename = "rpd")]
+    PerDay,
+}
+
+/// A newly created virtual key.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct VirtualKeyCreated {
+    /// ID of the virtual key.
+    #[serde(default)]
+    pub id: Option<String>,
+    /// Slug used to reference the virtual key.
+    pub slug: String,
+}
+
+/// The virtual keys of a workspace.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct VirtualKeyList {
+    /// Number of virtual keys.
+    #[serde(default)]
+    pub total: Option<u64>,
+    /// The virtual keys.
+    pub data: Vec<VirtualKey>,
+}
+
+/// A virtual key. The provider API key itself is not returned.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct VirtualKey {
+    /// Slug used to reference the virtual key.
+    pub slug: String,
+    /// Display name of the virtual key.
+    pub name: String,
+    /// Provider the key belongs to.
+    pub provider: VirtualKeyProvider,
+    /// Free-form note shown in Portkey.
+    #[serde(default)]
+    pub note: Option<String>,
+    /// Status of the key, e.g. `active` or `exhausted`.
+    #[serde(default)]
+    pub status: Option<String>,
+    /// Budget of the key.
+    #[serde(default)]
+    pub usage_limits: Option<UsageLimits>,
+    /// Rate limits of the key.
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub rate_limits: Vec<RateLimit>,
+    /// Creation time of the key.
+    #[serde(default)]
+    pub created_at: Option<String>,
+    /// All other fields of the key.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Deserializes `null` as an empty list.
+fn null_as_empty<'de, D>(deserializer: D) -> std::result::Result<Vec<RateLimit>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(Option::deserialize(deserializer)?.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn provider_round_trips_through_slug() {
+        for (provider, slug) in [
+            (VirtualKeyProvider::OpenAI, "openai"),
+            (VirtualKeyProvider::AzureOpenAI, "azure-openai"),
+            (VirtualKeyProvider::VertexAi, "vertex-ai"),
+            (VirtualKeyProvider::MistralAi, "mistral-ai"),
+            (
+                VirtualKeyProvider::Other("together-ai".into()),
+                "together-ai",
+            ),
+        ] {
+            assert_eq!(serde_json::to_value(&provider).unwrap(), json!(slug));
+            assert_eq!(
+                serde_json::from_value::<VirtualKeyProvider>(json!(slug)).unwrap(),
+                provider
+            );
+        }
+    }
+
+    #[test]
+    fn serializes_create_request() {
+        let request = CreateVirtualKeyRequest::new(
+            "azure-prod",
+            VirtualKeyProvider::AzureOpenAI,
+            "azure-key",
+        )
+        .azure_deployment("my-resource", "gpt-4o", "2024-06-01")
+        .usage_limits(UsageLimits::new(100.0).periodic_reset(ResetPeriod::Monthly))
+        .rate_limit(RateLimit::requests(600, RateLimitUnit::PerMinute));
+
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            json!({
+                "name": "azure-prod",
+                "provider": "azure-openai",
+                "key": "azure-key",
+                "resourceName": "my-resource",
+                "deploymentName": "gpt-4o",
+                "apiVersion": "2024-06-01",
+                "usage_limits": { "credit_limit": 100.0, "periodic_reset": "monthly" },
+                "rate_limits": [{ "type": "requests", "unit": "rpm", "value": 600 }]
+            })
+        );
+        assert!(!format!("{request:?}").contains("azure-key"));
+    }
+
+    #[test]
+    fn serializes_rate_limit() {
+        assert_eq!(
+            serde_json::to_value(RateLimit::requests(600, RateLimitUnit::PerMinute)).unwrap(),
+            json!({ "type": "requests", "unit": "rpm", "value": 600 })
+        );
+        assert_eq!(
+            serde_json::to_value(RateLimit::tokens(10_000, RateLimitUnit::PerDay)).unwrap(),
+            json!({ "type": "tokens", "unit": "rpd", "value": 10_000 })
+        );
+    }
+
+    #[test]
+    fn deserializes_null_rate_limits_as_empty() {
+        let key: VirtualKey = serde_json::from_value(json!({
+            "slug": "openai-prod-1a2b",
+            "name": "openai-prod",
+            "provider": "openai",
+            "rate_limits": null,
+            "model_config": {}
+        }))
+        .unwrap();
+
+        assert!(key.rate_limits.is_empty());
+        assert_eq!(key.provider, VirtualKeyProvider::OpenAI);
+        assert!(key.extra.contains_key("model_config"));
+
+        let key: VirtualKey = serde_json::from_value(json!({
+            "slug": "openai-prod-1a2b",
+            "name": "openai-prod",
+            "provider": "openai",
+            "rate_limits": [{ "type": "tokens", "unit": "rph", "value": 5 }]
+        }))
+        .unwrap();
+        assert_eq!(
+            key.rate_limits,
+            [RateLimit::tokens(5, RateLimitUnit::PerHour)]
+        );
+    }
+}
@@ -0,0 +1,168 @@
+//! Authentication modes for requests sent through the Portkey gateway.
+
+use std::fmt;
+
+use reqwest::header::HeaderMap;
+
+use crate::{headers, Result};
+
+/// How the gateway authenticates against the upstream provider.
+///
+/// The Portkey API key set with [`ClientBuilder::api_key`](crate::ClientBuilder::api_key)
+/// is sent as `x-portkey-api-key` in every mode; `Auth` selects the additional
+/// headers that tell the gateway which provider credentials to use.
+///
+/// # Examples
+///
+/// ```rust
+/// use portkey::{Auth, Client};
+///
+/// let client = Client::builder()
+///     .api_key("your-portkey-api-key")
+///     .auth(Auth::Provider {
+///         provider: "openai".into(),
+///         api_key: "sk-your-openai-key".into(),
+///     })
+///     .build()
+///     .expect("valid Portkey configuration");
+/// ```
+#[derive(Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Auth {
+    /// A Portkey virtual key, sent as `x-portkey-virtual-key`.
+    VirtualKey(String),
+    /// A provider name, sent as `x-portkey-provider`, with the raw provider
+    /// API key sent in the `Authorization` header.
+    Provider {
+        /// Provider name, e.g. `openai` or `anthropic`.
+        provider: String,
+        /// API key of the provider.
+        api_key: String,
+    },
+    /// A saved gateway config that carries the provider credentials, sent as
+    /// `x-portkey-config`.
+    ConfigId(String),
+    /// No provider credentials, e.g. for a self-hosted gateway that holds
+    /// them itself.
+    #[default]
+    None,
+}
+
+impl fmt::Debug for Auth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::VirtualKey(virtual_key) => {
+                f.debug_tuple("VirtualKey").field(virtual_key).finish()
+            }
+            Self::Provider { provider, .. } => f
+                .debug_struct("Provider")
+                .field("provider", provider)
+                .field("api_key", &"<redacted>")
+                .finish(),
+            Self::ConfigId(config_id) => f.debug_tuple("ConfigId").field(config_id).finish(),
+            Self::None => f.write_str("None"),
+        }
+    }
+}
+
+impl Auth {
+    /// Returns the saved config ID of [`Auth::ConfigId`].
+    pub(crate) fn config_id(&self) -> Option<&str> {
+        match self {
+            Self::ConfigId(config_id) => Some(config_id),
+            _ => None,
+        }
+    }
+
+    /// Returns the key sent in the `Authorization` header.
+    ///
+    /// This is the provider key in [`Auth::Provider`] mode and the Portkey API
+    /// key otherwise.
+    pub(crate) fn bearer_token<'a>(&'a self, portkey_api_key: &'a str) -> &'a str {
+        match self {
+            Self::Provider { api_key, .. } => api_key,
+            _ => portkey_api_key,
+        }
+    }
+
+    /// Inserts the mode-specific headers.
+    pub(crate) fn apply(&self, map: &mut HeaderMap) -> Result<()> {
+        match self {
+            Self::VirtualKey(virtual_key) => {
+                headers::insert(map, headers::VIRTUAL_KEY, virtual_key)
+            }
+            Self::Provider { provider, .. } => headers::insert(map, headers::PROVIDER, provider),
+            Self::ConfigId(config_id) => headers::insert(map, headers::CONFIG, config_id),
+            Self::None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{headers::tests::assert_headers, Client};
+
+    /// Returns the headers a client authenticating with `auth` sends.
+    fn client_headers(auth: Auth) -> HeaderMap {
+        let client = Client::builder()
+            .api_key("portkey-key")
+            .auth(auth)
+            .build()
+            .unwrap();
+        client.config.request_headers()
+    }
+
+    #[test]
+    fn virtual_key_headers() {
+        assert_headers(
+            &client_headers(Auth::VirtualKey("virtual-key".into())),
+            &[
+                ("authorization", "Bearer portkey-key"),
+                ("x-portkey-api-key", "portkey-key"),
+                ("x-portkey-virtual-key", "virtual-key"),
+            ],
+        );
+    }
+
+    #[test]
+    fn provider_headers() {
+        let auth = Auth::Provider {
+            provider: "openai".into(),
+            api_key: "sk-openai".into(),
+        };
+        assert_eq!(auth.bearer_token("portkey-key"), "sk-openai");
+        assert_headers(
+            &client_headers(auth),
+            &[
+                ("authorization", "Bearer sk-openai"),
+                ("x-portkey-api-key", "portkey-key"),
+                ("x-portkey-provider", "openai"),
+            ],
+        );
+    }
+
+    #[test]
+    fn config_id_headers() {
+        assert_headers(
+            &client_headers(Auth::ConfigId("pc-config".into())),
+            &[
+                ("authorization", "Bearer portkey-key"),
+                ("x-portkey-api-key", "portkey-key"),
+                ("x-portkey-config", "pc-config"),
+            ],
+        );
+    }
+
+    #[test]
+    fn no_auth_headers() {
+        assert_eq!(Auth::None.bearer_token("portkey-key"), "portkey-key");
+        assert_headers(
+            &client_headers(Auth::None),
+            &[
+                ("authorization", "Bearer portkey-key"),
+                ("x-portkey-api-key", "portkey-key"),
+            ],
+        );
+    }
+}
@@ -0,0 +1,516 @@
+//! Builder for configuring a [`Client`].
+
+use std::{fmt, time::Duration};
+
+use reqwest::{
+    header::{HeaderMap, AUTHORIZATION},
+    Client as ReqwestClient,
+};
+use url::Url;
+
+use crate::{
+    config::{CacheConfig, Config, ConfigSource, RetryConfig},
+    headers, Auth, Client, Error, Metadata, PortkeyConfig, ProviderOptions, Result, RetryPolicy,
+    BASE_URL,
+};
+
+/// A builder for creating a customized [`Client`].
+///
+/// Use the builder when the defaults of [`Client::new`] are not sufficient, for
+/// example to point the SDK at a self-hosted Portkey gateway.
+///
+/// # Examples
+///
+/// ```rust
+/// use portkey::ClientBuilder;
+///
+/// let client = ClientBuilder::new()
+///     .api_key("your-portkey-api-key")
+///     .virtual_key("your-portkey-virtual-key")
+///     .base_url("http://localhost:8787/v1")
+///     .build()
+///     .expect("valid Portkey configuration");
+/// ```
+#[derive(Clone)]
+pub struct ClientBuilder {
+    /// Portkey API key.
+    api_key: String,
+    /// How the gateway authenticates against the provider.
+    auth: Auth,
+    /// Provider-specific options replacing `auth`.
+    provider_options: Option<ProviderOptions>,
+    /// Base URL of the Portkey gateway.
+    base_url: String,
+    /// Gateway config, sent as `x-portkey-config` when set.
+    config: Option<ConfigSource>,
+    /// Response caching added to the gateway config.
+    cache: Option<CacheConfig>,
+    /// Gateway retries added to the gateway config.
+    retry: Option<RetryConfig>,
+    /// Gateway request timeout added to the gateway config.
+    gateway_timeout: Option<Duration>,
+    /// Timeout for establishing a connection to the gateway.
+    connect_timeout: Option<Duration>,
+    /// Timeout for a whole request, from connecting to reading the response.
+    timeout: Option<Duration>,
+    /// Default trace ID, sent as `x-portkey-trace-id` when set.
+    trace_id: Option<String>,
+    /// Metadata sent as `x-portkey-metadata`.
+    metadata: Option<Metadata>,
+    /// Whether requests without a trace ID get a generated one.
+    auto_trace_id: bool,
+    /// Client-side retries of failed requests.
+    retry_policy: Option<RetryPolicy>,
+    /// HTTP client provided by the application.
+    http_client: Option<ReqwestClient>,
+    /// Middleware stack for requests sent by the SDK.
+    #[cfg(feature = "middleware")]
+    middleware: Option<reqwest_middleware::ClientWithMiddleware>,
+}
+
+impl fmt::Debug for ClientBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("ClientBuilder");
+        debug
+            .field("api_key", &"<redacted>")
+            .field("auth", &self.auth)
+            .field("provider_options", &self.provider_options)
+            .field("base_url", &self.base_url)
+            .field("config", &self.config)
+            .field("cache", &self.cache)
+            .field("retry", &self.retry)
+            .field("gateway_timeout", &self.gateway_timeout)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("timeout", &self.timeout)
+            .field("trace_id", &self.trace_id)
+            .field("metadata", &self.metadata)
+            .field("auto_trace_id", &self.auto_trace_id)
+            .field("retry_policy", &self.retry_policy)
+            .field("http_client", &self.http_client);
+        #[cfg(feature = "middleware")]
+        debug.field("middleware", &self.middleware);
+        debug.finish()
+    }
+}
+
+impl Default for ClientBuilder {
+    fn default() -> Self {
+        Self {
+            api_key: String::new(),
+            auth: Auth::None,
+            provider_options: None,
+            base_url: BASE_URL.to_string(),
+            config: None,
+            cache: None,
+            retry: None,
+            gateway_timeout: None,
+            connect_timeout: None,
+            timeout: None,
+            trace_id: None,
+            metadata: None,
+            auto_trace_id: false,
+            retry_policy: None,
+            http_client: None,
+            #[cfg(feature = "middleware")]
+            middleware: None,
+        }
+    }
+}
+
+impl ClientBuilder {
+    /// Creates a new builder targeting the hosted Portkey API.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the Portkey API key, sent as `x-portkey-api-key`.
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = api_key.into();
+        self
+    }
+
+    /// Sets the Portkey virtual key.
+    ///
+    /// This is a shortcut for `auth(Auth::VirtualKey(virtual_key))`.
+    pub fn virtual_key(self, virtual_key: impl Into<String>) -> Self {
+        self.auth(Auth::VirtualKey(virtual_key.into()))
+    }
+
+    /// Sets how the gateway authenticates against the provider.
+    ///
+    /// Defaults to [`Auth::None`].
+    pub fn auth(mut self, auth: Auth) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Sets provider-specific options, e.g. for Azure OpenAI.
+    ///
+    /// Provider options carry their own credentials and cannot be combined with
+    /// an [`Auth`] mode other than [`Auth::None`].
+    pub fn provider_options(mut self, provider_options: ProviderOptions) -> Self {
+        self.provider_options = Some(provider_options);
+        self
+    }
+
+    /// Sets the base URL of the Portkey gateway.
+    ///
+    /// Defaults to `https://api.portkey.ai/v1`. Use this to target a self-hosted
+    /// gateway, e.g. `http://localhost:8787/v1`. A trailing slash is ignored.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Sets the gateway config sent inline with every request.
+    ///
+    /// Replaces a config ID set with [`ClientBuilder::config_id`].
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = Some(ConfigSource::Inline(Box::new(config)));
+        self
+    }
+
+    /// Sets the ID of a gateway config saved in the Portkey dashboard.
+    ///
+    /// Replaces a config set with [`ClientBuilder::config`].
+    pub fn config_id(mut self, config_id: impl Into<String>) -> Self {
+        self.config = Some(ConfigSource::Saved(config_id.into()));
+        self
+    }
+
+    /// Enables response caching for every request.
+    ///
+    /// The cache settings are added to the inline gateway config, creating one
+    /// if none is set. They cannot be combined with a saved config ID.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use portkey::{config::CacheConfig, Client};
+    ///
+    /// let client = Client::builder()
+    ///     .api_key("your-portkey-api-key")
+    ///     .virtual_key("your-portkey-virtual-key")
+    ///     .cache(CacheConfig::simple().max_age(Duration::from_secs(86_400)))
+    ///     .build()
+    ///     .expect("valid Portkey configuration");
+    /// ```
+    pub fn cache(mut self, cache: CacheConfig) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Enables gateway retries for every request.
+    ///
+    /// The retry settings are added to the inline gateway config, creating one
+    /// if none is set. They cannot be combined with a saved config ID. Use
+    /// [`Target::retry`](crate::config::Target::retry) to configure retries
+    /// per target.
+    pub fn retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    /// Sets the time after which the gateway abandons a request to a
+    /// provider, treating it as failed so that fallbacks and retries apply.
+    ///
+    /// The timeout is added to the inline gateway config, creating one if none
+    /// is set. It cannot be combined with a saved config ID. Use
+    /// [`Target::request_timeout`](crate::config::Target::request_timeout) to
+    /// set timeouts per target.
+    pub fn gateway_timeout(mut self, gateway_timeout: Duration) -> Self {
+        self.gateway_timeout = Some(gateway_timeout);
+        self
+    }
+
+    /// Sets the timeout for establishing a connection to the gateway.
+    ///
+    /// No timeout is applied by default.
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Sets the timeout for a whole request, from connecting until the
+    /// response body has been read.
+    ///
+    /// No timeout is applied by default. For streamed responses the timeout
+    /// covers the entire stream.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use portkey::Client;
+    ///
+    /// let client = Client::builder()
+    ///     .api_key("your-portkey-api-key")
+    ///     .virtual_key("your-portkey-virtual-key")
+    ///     .connect_timeout(Duration::from_secs(5))
+    ///     .timeout(Duration::from_secs(60))
+    ///     .gateway_timeout(Duration::from_secs(30))
+    ///     .build()
+    ///     .expect("valid Portkey configuration");
+    /// ```
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the default trace ID sent as `x-portkey-trace-id`.
+    ///
+    /// Individual requests can override it with
+    /// [`RequestOptions::trace_id`](crate::RequestOptions::trace_id).
+    pub fn trace_id(mut self, trace_id: impl Into<String>) -> Self {
+        self.trace_id = Some(trace_id.into());
+        self
+    }
+
+    /// Generates a UUID trace ID for every request that has no trace ID set.
+    ///
+    /// The generated ID is returned by
+    /// [`ResponseMeta::trace_id`](crate::ResponseMeta::trace_id) for calls made
+    /// through the SDK, and is kept across client-side retries of a call.
+    ///
+    /// Calls made through [`Client::openai`] get a new ID for every attempt,
+    /// since `async-openai` rebuilds the headers when it retries, e.g. after a
+    /// `429`. To log all attempts of such a call under one ID, set it
+    /// explicitly with
+    /// [`RequestOptions::generate_trace_id`](crate::RequestOptions::generate_trace_id).
+    pub fn auto_trace_id(mut self, auto_trace_id: bool) -> Self {
+        self.auto_trace_id = auto_trace_id;
+        self
+    }
+
+    /// Sets the default metadata sent as `x-portkey-metadata`.
+    ///
+    /// Individual requests can replace it with
+    /// [`RequestOptions::metadata`](crate::RequestOptions::metadata).
+    pub fn metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// Enables client-side retries of requests that fail before reaching the
+    /// gateway.
+    ///
+    /// Disabled by default. See [`RetryPolicy`] for which failures are retried.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Uses an existing HTTP client, e.g. one configured with a proxy, custom
+    /// root certificates or connection pool limits.
+    ///
+    /// Portkey headers are added to each request, so the client's default
+    /// headers are left untouched. Timeouts must be configured on the provided
+    /// client; [`ClientBuilder::connect_timeout`] and [`ClientBuilder::timeout`]
+    /// cannot be combined with it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use portkey::Client;
+    ///
+    /// let http_client = reqwest::Client::builder()
+    ///     .pool_max_idle_per_host(8)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let client = Client::builder()
+    ///     .api_key("your-portkey-api-key")
+    ///     .virtual_key("your-portkey-virtual-key")
+    ///     .http_client(http_client)
+    ///     .build()
+    ///     .expect("valid Portkey configuration");
+    /// ```
+    pub fn http_client(mut self, http_client: ReqwestClient) -> Self {
+        self.http_client = Some(http_client);
+        self
+    }
+
+    /// Sends requests made by the SDK, e.g. [`Client::chat`], through a
+    /// `reqwest-middleware` stack.
+    ///
+    /// The [`async_openai`] client returned by [`Client::openai`] cannot use
+    /// middleware and keeps using the plain HTTP client, see
+    /// [`ClientBuilder::http_client`].
+    ///
+    /// Requests sent through the stack use its own HTTP client, so timeouts
+    /// must be configured on that client; [`ClientBuilder::connect_timeout`]
+    /// and [`ClientBuilder::timeout`] cannot be combined with middleware.
+    #[cfg(feature = "middleware")]
+    pub fn middleware(mut self, middleware: reqwest_middleware::ClientWithMiddleware) -> Self {
+        self.middleware = Some(middleware);
+        self
+    }
+
+    /// Builds the [`Client`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the base URL is not an `http` or `https` URL
+    /// with a host, if the gateway config or metadata is invalid, if the
+    /// provider options conflict with the authentication mode, if timeouts are
+    /// combined with a provided HTTP client or middleware stack, if a
+    /// configured value is not a valid header value or if the underlying HTTP
+    /// client cannot be built.
+    pub fn build(mut self) -> Result<Client> {
+        let base_url = Url::parse(&self.base_url).map_err(|source| Error::InvalidUrl {
+            url: self.base_url.clone(),
+            source: Some(source),
+        })?;
+        // Strings such as `localhost:8787/v1` parse with `localhost` as their
+        // scheme, but cannot be requested.
+        if !matches!(base_url.scheme(), "http" | "https") || !base_url.has_host() {
+            return Err(Error::InvalidUrl {
+                url: self.base_url.clone(),
+                source: None,
+            });
+        }
+
+        if let Some(cache) = self.cache.take() {
+            self.config = Some(ConfigSource::update(
+                self.config.take(),
+                "cache settings",
+                |config| config.cache(cache),
+            )?);
+        }
+        if let Some(retry) = self.retry.take() {
+            self.config = Some(ConfigSource::update(
+                self.config.take(),
+                "retry settings",
+                |config| config.retry(retry),
+            )?);
+        }
+        if let Some(gateway_timeout) = self.gateway_timeout.take() {
+            self.config = Some(ConfigSource::update(
+                self.config.take(),
+                "a request timeout",
+                |config| config.request_timeout(gateway_timeout),
+            )?);
+        }
+        if self.auth.config_id().is_some() && self.config.is_some() {
+            return Err(Error::InvalidConfig(
+                "a gateway config cannot be combined with `Auth::ConfigId`".to_string(),
+            ));
+        }
+
+        if self.provider_options.is_some() && self.auth != Auth::None {
+            return Err(Error::InvalidProviderOptions(
+                "provider options cannot be combined with an `Auth` mode".to_string(),
+            ));
+        }
+
+        let mut headers = HeaderMap::new();
+        if !self.api_key.is_empty() {
+            headers::insert(&mut headers, headers::API_KEY, &self.api_key)?;
+        }
+        let bearer_token = self.auth.bearer_token(&self.api_key).to_string();
+        if !bearer_token.is_empty() {
+            headers::insert(
+                &mut headers,
+                AUTHORIZATION.as_str(),
+                &format!("Bearer {bearer_token}"),
+            )?;
+        }
+        self.auth.apply(&mut headers)?;
+        if let Some(provider_options) = &self.provider_options {
+            provider_options.apply(&mut headers)?;
+        }
+        if let Some(trace_id) = &self.trace_id {
+            headers::insert(&mut headers, headers::TRACE_ID, trace_id)?;
+        }
+        if let Some(metadata) = &self.metadata {
+            headers::insert(&mut headers, headers::METADATA, &metadata.header_value()?)?;
+        }
+        if let Some(config) = &self.config {
+            headers::insert(&mut headers, headers::CONFIG, &config.header_value()?)?;
+        }
+        #[cfg(feature = "middleware")]
+        if self.middleware.is_some() && (self.connect_timeout.is_some() || self.timeout.is_some()) {
+            return Err(Error::InvalidArgument(
+                "timeouts must be configured on the HTTP client of the middleware stack"
+                    .to_string(),
+            ));
+        }
+        let http = match self.http_client {
+            Some(_) if self.connect_timeout.is_some() || self.timeout.is_some() => {
+                return Err(Error::InvalidArgument(
+                    "timeouts must be configured on the provided HTTP client".to_string(),
+                ));
+            }
+            Some(http_client) => http_client,
+            None => {
+                let mut http = ReqwestClient::builder();
+                if let Some(connect_timeout) = self.connect_timeout {
+                    http = http.connect_timeout(connect_timeout);
+                }
+                if let Some(timeout) = self.timeout {
+                    http = http.timeout(timeout);
+                }
+                http.build().map_err(Error::HttpClient)?
+            }
+        };
+
+        let config = PortkeyConfig::new(self.base_url, bearer_token, headers)
+            .with_auto_trace_id(self.auto_trace_id)
+            .with_provider_options(self.provider_options);
+
+        let gateway_config = self.config.or_else(|| {
+            self.auth
+                .config_id()
+                .map(|config_id| ConfigSource::Saved(config_id.to_string()))
+        });
+
+        Ok(Client {
+            http,
+            config,
+            gateway_config,
+            retry_policy: self.retry_policy,
+            #[cfg(feature = "middleware")]
+            middleware: self.middleware,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Strategy, Target};
+
+    #[test]
+    fn rejects_base_urls_without_http_scheme_or_host() {
+        for base_url in ["localhost:8787/v1", "mailto:x@y", "file:///v1", "not a url"] {
+            let result = Client::builder().base_url(base_url).build();
+            assert!(
+                matches!(result, Err(Error::InvalidUrl { .. })),
+                "{base_url} was accepted"
+            );
+        }
+        for base_url in ["http://localhost:8787/v1", "https://api.portkey.ai/v1/"] {
+            assert!(Client::builder().base_url(base_url).build().is_ok());
+        }
+    }
+
+    #[test]
+    fn debug_redacts_inline_config_credentials() {
+        let client = Client::builder()
+            .api_key("pk-SECRET")
+            .config(
+                Config::new()
+                    .strategy(Strategy::fallback([]))
+                    .target(Target::new().provider("openai").api_key("sk-SECRET")),
+            )
+            .build()
+            .unwrap();
+
+        let debug = format!("{client:?}");
+        assert!(!debug.contains("pk-SECRET"));
+        assert!(!debug.contains("sk-SECRET"));
+    }
+}
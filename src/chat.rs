@@ -0,0 +1,76 @@
+//! Chat completions sent through the Portkey gateway.
+
+use async_openai::types::{CreateChatCompletionRequest, CreateChatCompletionResponse};
+
+use crate::{Client, Error, PortkeyResponse, Result};
+
+/// Chat completions API.
+///
+/// Unlike the [`async_openai`] client returned by [`Client::openai`], errors
+/// are returned as [`Error::Api`] with the gateway's status, headers and
+/// error body.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use async_openai::types::{
+///     ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs,
+/// };
+/// use portkey::{Client, Error};
+///
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::new("your-portkey-api-key", "your-portkey-virtual-key");
+///
+/// let request = CreateChatCompletionRequestArgs::default()
+///     .model("gpt-4o")
+///     .messages([ChatCompletionRequestUserMessageArgs::default()
+///         .content("Hello, Portkey!")
+///         .build()?
+///         .into()])
+///     .build()?;
+///
+/// match client.chat().create(request).await {
+///     Ok(response) => println!("{:?}", response.choices),
+///     Err(Error::Api(error)) => {
+///         println!("{} failed with {}", error.provider().unwrap_or("gateway"), error.status())
+///     }
+///     Err(error) => return Err(error.into()),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Chat<'c> {
+    client: &'c Client,
+}
+
+impl<'c> Chat<'c> {
+    pub(crate) fn new(client: &'c Client) -> Self {
+        Self { client }
+    }
+
+    /// Creates a chat completion.
+    ///
+    /// The response carries Portkey's response metadata, see
+    /// [`PortkeyResponse::meta`].
+    ///
+    /// Streaming is not supported; use `client.openai().chat().create_stream`
+    /// for streamed responses.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if `request.stream` is set,
+    /// [`Error::Api`] if the gateway returns an error response and
+    /// [`Error::Request`] if the request fails.
+    pub async fn create(
+        &self,
+        request: CreateChatCompletionRequest,
+    ) -> Result<PortkeyResponse<CreateChatCompletionResponse>> {
+        if request.stream == Some(true) {
+            return Err(Error::InvalidArgument(
+                "streaming is not supported by `Chat::create`".to_string(),
+            ));
+        }
+        self.client.post("/chat/completions", &request).await
+    }
+}
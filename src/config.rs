@@ -0,0 +1,860 @@
+//! Typed Portkey gateway configuration.
+//!
+//! A [`Config`] describes how the Portkey gateway routes a request: which
+//! [`Strategy`] to apply and which [`Target`]s to route to. It is attached to a
+//! [`Client`](crate::Client) through [`ClientBuilder::config`](crate::ClientBuilder::config)
+//! and sent as JSON in the `x-portkey-config` header. Configs saved in the
+//! Portkey dashboard can be referenced by ID with
+//! [`ClientBuilder::config_id`](crate::ClientBuilder::config_id) instead.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use portkey::config::{Config, OverrideParams, Strategy, Target};
+//!
+//! let config = Config::new()
+//!     .strategy(Strategy::Single)
+//!     .target(
+//!         Target::new()
+//!             .virtual_key("openai-virtual-key")
+//!             .override_params(OverrideParams::new().model("gpt-4o")),
+//!     );
+//!
+//! let client = portkey::Client::builder()
+//!     .api_key("your-portkey-api-key")
+//!     .config(config)
+//!     .build()
+//!     .expect("valid Portkey configuration");
+//! ```
+
+mod condition;
+
+pub use condition::{Condition, Field, Operator, Query};
+
+use std::{fmt, time::Duration};
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::{Error, Result};
+
+/// A Portkey gateway config.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Config {
+    /// Routing strategy applied to `targets`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    strategy: Option<Strategy>,
+    /// Targets the gateway routes to.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    targets: Vec<Target>,
+    /// Request parameters overridden for every target.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    override_params: Option<OverrideParams>,
+    /// Response caching for every target.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cache: Option<CacheConfig>,
+    /// Gateway retries for every target.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    retry: Option<RetryConfig>,
+    /// Time in milliseconds after which the gateway abandons a request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_timeout: Option<u64>,
+}
+
+impl Config {
+    /// Creates an empty config.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the routing strategy.
+    pub fn strategy(mut self, strategy: Strategy) -> Self {
+        self.strategy = Some(strategy);
+        self
+    }
+
+    /// Appends a target.
+    pub fn target(mut self, target: Target) -> Self {
+        self.targets.push(target);
+        self
+    }
+
+    /// Replaces all targets.
+    pub fn targets(mut self, targets: impl IntoIterator<Item = Target>) -> Self {
+        self.targets = targets.into_iter().collect();
+        self
+    }
+
+    /// Sets the parameters overridden for every target.
+    pub fn override_params(mut self, override_params: OverrideParams) -> Self {
+        self.override_params = Some(override_params);
+        self
+    }
+
+    /// Sets response caching for every target.
+    pub fn cache(mut self, cache: CacheConfig) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Sets gateway retries for every target.
+    pub fn retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    /// Sets the time after which the gateway abandons a request to a target.
+    ///
+    /// A timed out request is treated as failed, so it triggers a fallback or
+    /// retry. Sub-millisecond precision is discarded.
+    pub fn request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request_timeout = Some(duration_millis(request_timeout));
+        self
+    }
+
+    /// Checks the config for mistakes the gateway would reject.
+    ///
+    /// This is called by [`ClientBuilder::build`](crate::ClientBuilder::build),
+    /// so invalid configs are caught when the client is constructed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidConfig`] describing the first problem found.
+    pub fn validate(&self) -> Result<()> {
+        validate_cache(self.cache.as_ref(), "config")?;
+        validate_retry(self.retry.as_ref(), "config")?;
+        validate_request_timeout(self.request_timeout, "config")?;
+        validate_routing(self.strategy.as_ref(), &self.targets, "config")
+    }
+}
+
+/// The routing strategy of a [`Config`] or nested [`Target`].
+///
+/// Serialized as the `strategy` object of a Portkey config.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "mode", rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum Strategy {
+    /// Route every request to the single configured target.
+    Single,
+    /// Try the targets in order, moving on to the next target when a request
+    /// fails.
+    Fallback {
+        /// Status codes that trigger a fallback. When empty, any non-2xx
+        /// response triggers a fallback.
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        on_status_codes: Vec<u16>,
+    },
+    /// Distribute requests across the targets according to their
+    /// [weights](Target::weight).
+    #[serde(rename = "loadbalance")]
+    LoadBalance,
+    /// Route each request to the target named by the first matching
+    /// [`Condition`], or to the `default` target when none matches.
+    Conditional {
+        /// Conditions evaluated in order.
+        conditions: Vec<Condition>,
+        /// Name of the target used when no condition matches.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        default: Option<String>,
+    },
+}
+
+impl Strategy {
+    /// Creates a [`Strategy::Fallback`] triggered by the given status codes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use portkey::config::{Config, Strategy, Target};
+    ///
+    /// let config = Config::new()
+    ///     .strategy(Strategy::fallback([429, 500, 502, 503]))
+    ///     .target(Target::new().virtual_key("openai-virtual-key"))
+    ///     .target(Target::new().virtual_key("anthropic-virtual-key"))
+    ///     .target(Target::new().provider("ollama"));
+    ///
+    /// assert!(config.validate().is_ok());
+    /// ```
+    pub fn fallback(on_status_codes: impl IntoIterator<Item = u16>) -> Self {
+        Self::Fallback {
+            on_status_codes: on_status_codes.into_iter().collect(),
+        }
+    }
+
+    /// Creates a [`Strategy::Conditional`] with the given conditions and the
+    /// name of the default target.
+    ///
+    /// Conditions and the default refer to targets by their [name](Target::name).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use portkey::config::{Condition, Config, Query, Strategy, Target};
+    ///
+    /// let config = Config::new()
+    ///     .strategy(Strategy::conditional(
+    ///         [Condition::new(Query::metadata("user_plan").eq("paid"), "premium")],
+    ///         "standard",
+    ///     ))
+    ///     .target(Target::new().name("premium").virtual_key("openai-gpt-4o"))
+    ///     .target(Target::new().name("standard").virtual_key("openai-gpt-4o-mini"));
+    ///
+    /// assert!(config.validate().is_ok());
+    /// ```
+    pub fn conditional(
+        conditions: impl IntoIterator<Item = Condition>,
+        default: impl Into<String>,
+    ) -> Self {
+        Self::Conditional {
+            conditions: conditions.into_iter().collect(),
+            default: Some(default.into()),
+        }
+    }
+}
+
+/// A target the gateway can route a request to.
+///
+/// A target either points at a provider (through a virtual key or a provider
+/// name and API key) or nests further targets with its own [`Strategy`].
+#[derive(Clone, Default, PartialEq, Serialize)]
+pub struct Target {
+    /// Name used to reference this target.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    /// Portkey virtual key of the provider.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    virtual_key: Option<String>,
+    /// Provider name, e.g. `openai`, used together with `api_key`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provider: Option<String>,
+    /// Provider API key, used together with `provider`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    api_key: Option<String>,
+    /// Share of traffic routed to this target under [`Strategy::LoadBalance`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    weight: Option<f32>,
+    /// Request parameters overridden for this target.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    override_params: Option<OverrideParams>,
+    /// Response caching for this target.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cache: Option<CacheConfig>,
+    /// Gateway retries for this target.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    retry: Option<RetryConfig>,
+    /// Time in milliseconds after which the gateway abandons a request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_timeout: Option<u64>,
+    /// Routing strategy applied to the nested `targets`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    strategy: Option<Strategy>,
+    /// Nested targets.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    targets: Vec<Target>,
+}
+
+impl fmt::Debug for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Target")
+            .field("name", &self.name)
+            .field("virtual_key", &self.virtual_key)
+            .field("provider", &self.provider)
+            .field("api_key", &self.api_key.as_ref().map(|_| "<redacted>"))
+            .field("weight", &self.weight)
+            .field("override_params", &self.override_params)
+            .field("cache", &self.cache)
+            .field("retry", &self.retry)
+            .field("request_timeout", &self.request_timeout)
+            .field("strategy", &self.strategy)
+            .field("targets", &self.targets)
+            .finish()
+    }
+}
+
+impl Target {
+    /// Creates an empty target.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the name used to reference this target.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the Portkey virtual key of the provider.
+    pub fn virtual_key(mut self, virtual_key: impl Into<String>) -> Self {
+        self.virtual_key = Some(virtual_key.into());
+        self
+    }
+
+    /// Sets the provider name, e.g. `openai` or `anthropic`.
+    pub fn provider(mut self, provider: impl Into<String>) -> Self {
+        self.provider = Some(provider.into());
+        self
+    }
+
+    /// Sets the provider API key.
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Sets the share of traffic routed to this target under
+    /// [`Strategy::LoadBalance`].
+    ///
+    /// Weights are relative to the other targets and must be positive. Targets
+    /// without a weight use the gateway default of `1`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use portkey::config::{Config, Strategy, Target};
+    ///
+    /// let config = Config::new()
+    ///     .strategy(Strategy::LoadBalance)
+    ///     .target(Target::new().virtual_key("openai-key-1").weight(0.7))
+    ///     .target(Target::new().virtual_key("openai-key-2").weight(0.3));
+    ///
+    /// assert!(config.validate().is_ok());
+    /// ```
+    pub fn weight(mut self, weight: f32) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
+    /// Sets the request parameters overridden for this target.
+    pub fn override_params(mut self, override_params: OverrideParams) -> Self {
+        self.override_params = Some(override_params);
+        self
+    }
+
+    /// Sets response caching for this target.
+    pub fn cache(mut self, cache: CacheConfig) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Sets gateway retries for this target.
+    pub fn retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    /// Sets the time after which the gateway abandons a request to this target.
+    ///
+    /// A timed out request is treated as failed, so a slow provider triggers
+    /// the next fallback target. Sub-millisecond precision is discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use portkey::config::{Config, Strategy, Target};
+    ///
+    /// let config = Config::new()
+    ///     .strategy(Strategy::fallback([408, 429, 500, 502, 503, 504]))
+    ///     .target(
+    ///         Target::new()
+    ///             .virtual_key("openai-virtual-key")
+    ///             .request_timeout(Duration::from_secs(10)),
+    ///     )
+    ///     .target(Target::new().virtual_key("anthropic-virtual-key"));
+    ///
+    /// assert!(config.validate().is_ok());
+    /// ```
+    pub fn request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request_timeout = Some(duration_millis(request_timeout));
+        self
+    }
+
+    /// Sets the routing strategy applied to the nested targets.
+    pub fn strategy(mut self, strategy: Strategy) -> Self {
+        self.strategy = Some(strategy);
+        self
+    }
+
+    /// Appends a nested target.
+    pub fn target(mut self, target: Target) -> Self {
+        self.targets.push(target);
+        self
+    }
+
+    /// Replaces all nested targets.
+    pub fn targets(mut self, targets: impl IntoIterator<Item = Target>) -> Self {
+        self.targets = targets.into_iter().collect();
+        self
+    }
+}
+
+/// Request parameters the gateway overrides before forwarding a request.
+///
+/// Common parameters have dedicated setters; any other parameter can be set
+/// with [`OverrideParams::insert`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct OverrideParams {
+    /// Model to use instead of the requested one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    model: Option<String>,
+    /// Sampling temperature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    /// Nucleus sampling probability mass.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    /// Maximum number of tokens to generate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
+    /// Any other overridden parameters.
+    #[serde(flatten)]
+    extra: Map<String, Value>,
+}
+
+impl OverrideParams {
+    /// Creates an empty set of override parameters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Overrides the model.
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    /// Overrides the sampling temperature.
+    pub fn temperature(mut self, temperature: f32) -> Self {
+        self.temperature = Some(temperature);
+        self
+    }
+
+    /// Overrides the nucleus sampling probability mass.
+    pub fn top_p(mut self, top_p: f32) -> Self {
+        self.top_p = Some(top_p);
+        self
+    }
+
+    /// Overrides the maximum number of tokens to generate.
+    pub fn max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Overrides an arbitrary request parameter.
+    pub fn insert(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.extra.insert(key.into(), value.into());
+        self
+    }
+}
+
+/// Response caching performed by the gateway.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use portkey::config::{CacheConfig, Config};
+///
+/// let config = Config::new().cache(CacheConfig::semantic().max_age(Duration::from_secs(3600)));
+/// assert!(config.validate().is_ok());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CacheConfig {
+    /// How requests are matched against cached responses.
+    mode: CacheMode,
+    /// How long responses are cached, in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_age: Option<u64>,
+}
+
+impl CacheConfig {
+    /// Creates a cache config with the given mode.
+    pub fn new(mode: CacheMode) -> Self {
+        Self {
+            mode,
+            max_age: None,
+        }
+    }
+
+    /// Creates a cache that serves responses to identical requests.
+    pub fn simple() -> Self {
+        Self::new(CacheMode::Simple)
+    }
+
+    /// Creates a cache that also serves responses to semantically similar
+    /// requests.
+    pub fn semantic() -> Self {
+        Self::new(CacheMode::Semantic)
+    }
+
+    /// Sets how long responses are cached.
+    ///
+    /// Portkey accepts at most 90 days; sub-second precision is discarded.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age.as_secs());
+        self
+    }
+}
+
+/// How the gateway matches requests against cached responses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum CacheMode {
+    /// Match identical requests.
+    Simple,
+    /// Match semantically similar requests.
+    Semantic,
+}
+
+/// Retries the gateway performs when a request to a target fails.
+///
+/// # Examples
+///
+/// ```rust
+/// use portkey::config::{Config, RetryConfig, Strategy, Target};
+///
+/// let config = Config::new()
+///     .strategy(Strategy::fallback([]))
+///     .target(
+///         Target::new()
+///             .virtual_key("openai-virtual-key")
+///             .retry(RetryConfig::new(3).on_status_codes([429, 503])),
+///     )
+///     .target(Target::new().virtual_key("anthropic-virtual-key"));
+///
+/// assert!(config.validate().is_ok());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RetryConfig {
+    /// Maximum number of retries.
+    attempts: u8,
+    /// Status codes that trigger a retry.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    on_status_codes: Vec<u16>,
+}
+
+impl RetryConfig {
+    /// Most retries Portkey performs for a request.
+    pub const MAX_ATTEMPTS: u8 = 5;
+
+    /// Creates a retry config with the given number of retries.
+    ///
+    /// At most [`RetryConfig::MAX_ATTEMPTS`] retries are accepted.
+    pub fn new(attempts: u8) -> Self {
+        Self {
+            attempts,
+            on_status_codes: Vec::new(),
+        }
+    }
+
+    /// Restricts retries to the given status codes.
+    ///
+    /// By default the gateway retries on 429, 500, 502, 503 and 504.
+    pub fn on_status_codes(mut self, on_status_codes: impl IntoIterator<Item = u16>) -> Self {
+        self.on_status_codes = on_status_codes.into_iter().collect();
+        self
+    }
+}
+
+/// How a [`Client`](crate::Client) references its gateway config.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ConfigSource {
+    /// A config sent inline as JSON.
+    Inline(Box<Config>),
+    /// The ID of a config saved in the Portkey dashboard.
+    Saved(String),
+}
+
+impl ConfigSource {
+    /// Returns the value of the `x-portkey-config` header.
+    pub(crate) fn header_value(&self) -> Result<String> {
+        match self {
+            Self::Inline(config) => {
+                config.validate()?;
+                Ok(serde_json::to_string(config)?)
+            }
+            Self::Saved(id) => Ok(id.clone()),
+        }
+    }
+
+    /// Returns `source` with `update` applied to its inline config, creating an
+    /// inline config if there is none.
+    ///
+    /// Saved configs cannot be modified, so `setting` is reported in an error.
+    pub(crate) fn update(
+        source: Option<Self>,
+        setting: &str,
+        update: impl FnOnce(Config) -> Config,
+    ) -> Result<Self> {
+        match source {
+            Some(Self::Inline(config)) => Ok(Self::Inline(Box::new(update(*config)))),
+            Some(Self::Saved(id)) => Err(Error::InvalidConfig(format!(
+                "{setting} cannot be added to the saved config `{id}`"
+            ))),
+            None => Ok(Self::Inline(Box::new(update(Config::new())))),
+        }
+    }
+}
+
+/// Longest cache duration Portkey accepts, 90 days.
+const MAX_CACHE_AGE: u64 = 90 * 24 * 60 * 60;
+
+/// Validates the cache settings of a config or target.
+fn validate_cache(cache: Option<&CacheConfig>, path: &str) -> Result<()> {
+    match cache.and_then(|cache| cache.max_age) {
+        Some(max_age) if max_age == 0 || max_age > MAX_CACHE_AGE => Err(Error::InvalidConfig(
+            format!("{path}: cache max age must be between 1 second and 90 days, got {max_age}s"),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Validates the gateway request timeout of a config or target.
+fn validate_request_timeout(request_timeout: Option<u64>, path: &str) -> Result<()> {
+    match request_timeout {
+        Some(0) => Err(Error::InvalidConfig(format!(
+            "{path}: request timeout must be at least 1 millisecond"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Converts a duration to whole milliseconds, saturating on overflow.
+fn duration_millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+/// Validates the retry settings of a config or target.
+fn validate_retry(retry: Option<&RetryConfig>, path: &str) -> Result<()> {
+    let Some(retry) = retry else {
+        return Ok(());
+    };
+    if retry.attempts > RetryConfig::MAX_ATTEMPTS {
+        return Err(Error::InvalidConfig(format!(
+            "{path}: at most {} retry attempts are allowed, got {}",
+            RetryConfig::MAX_ATTEMPTS,
+            retry.attempts
+        )));
+    }
+    validate_status_codes(&retry.on_status_codes, "retry", path)
+}
+
+/// Validates that `codes` are HTTP status codes.
+fn validate_status_codes(codes: &[u16], kind: &str, path: &str) -> Result<()> {
+    match codes.iter().find(|code| !(100..=599).contains(*code)) {
+        Some(code) => Err(Error::InvalidConfig(format!(
+            "{path}: invalid {kind} status code {code}"
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Validates a strategy together with the targets it applies to.
+fn validate_routing(strategy: Option<&Strategy>, targets: &[Target], path: &str) -> Result<()> {
+    if strategy.is_some() && targets.is_empty() {
+        return Err(Error::InvalidConfig(format!(
+            "{path}: a strategy requires at least one target"
+        )));
+    }
+    if strategy.is_none() && !targets.is_empty() {
+        return Err(Error::InvalidConfig(format!(
+            "{path}: targets require a strategy"
+        )));
+    }
+
+    match strategy {
+        Some(Strategy::Single) if targets.len() > 1 => {
+            return Err(Error::InvalidConfig(format!(
+                "{path}: the single strategy accepts exactly one target"
+            )));
+        }
+        Some(Strategy::Fallback { on_status_codes }) => {
+            validate_status_codes(on_status_codes, "fallback", path)?;
+        }
+        Some(Strategy::LoadBalance) => {
+            if let Some((index, weight)) = targets
+                .iter()
+                .enumerate()
+                .filter_map(|(index, target)| target.weight.map(|weight| (index, weight)))
+                .find(|(_, weight)| !(weight.is_finite() && *weight > 0.0))
+            {
+                return Err(Error::InvalidConfig(format!(
+                    "{path}.targets[{index}]: load balancing weight must be positive, got {weight}"
+                )));
+            }
+        }
+        Some(Strategy::Conditional {
+            conditions,
+            default,
+        }) => {
+            if conditions.is_empty() {
+                return Err(Error::InvalidConfig(format!(
+                    "{path}: the conditional strategy requires at least one condition"
+                )));
+            }
+            let referenced = conditions
+                .iter()
+                .map(Condition::then)
+                .chain(default.as_deref());
+            for name in referenced {
+                if !targets
+                    .iter()
+                    .any(|target| target.name.as_deref() == Some(name))
+                {
+                    return Err(Error::InvalidConfig(format!(
+                        "{path}: conditional routing references unknown target `{name}`"
+                    )));
+                }
+            }
+        }
+        _ => {}
+    }
+
+    for (index, target) in targets.iter().enumerate() {
+        let path = format!("{path}.targets[{index}]");
+        validate_cache(target.cache.as_ref(), &path)?;
+        validate_retry(target.retry.as_ref(), &path)?;
+        validate_request_timeout(target.request_timeout, &path)?;
+        validate_routing(target.strategy.as_ref(), &target.targets, &path)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    /// Returns the `x-portkey-config` header sent for `config`, parsed as JSON.
+    fn header(config: Config) -> Value {
+        let header = ConfigSource::Inline(Box::new(config))
+            .header_value()
+            .unwrap();
+        serde_json::from_str(&header).unwrap()
+    }
+
+    #[test]
+    fn fallback_config() {
+        let config = Config::new()
+            .strategy(Strategy::fallback([429, 503]))
+            .target(
+                Target::new()
+                    .virtual_key("openai-virtual-key")
+                    .retry(RetryConfig::new(3).on_status_codes([429])),
+            )
+            .target(Target::new().provider("ollama"));
+
+        assert_eq!(
+            header(config),
+            json!({
+                "strategy": { "mode": "fallback", "on_status_codes": [429, 503] },
+                "targets": [
+                    {
+                        "virtual_key": "openai-virtual-key",
+                        "retry": { "attempts": 3, "on_status_codes": [429] }
+                    },
+                    { "provider": "ollama" }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn loadbalance_config() {
+        let config = Config::new()
+            .strategy(Strategy::LoadBalance)
+            .target(Target::new().virtual_key("key-a").weight(0.7))
+            .target(Target::new().virtual_key("key-b").weight(0.3))
+            .cache(CacheConfig::semantic().max_age(Duration::from_secs(3600)));
+
+        assert_eq!(
+            header(config),
+            json!({
+                "strategy": { "mode": "loadbalance" },
+                "targets": [
+                    { "virtual_key": "key-a", "weight": 0.7 },
+                    { "virtual_key": "key-b", "weight": 0.3 }
+                ],
+                "cache": { "mode": "semantic", "max_age": 3600 }
+            })
+        );
+    }
+
+    #[test]
+    fn conditional_config() {
+        let config = Config::new()
+            .strategy(Strategy::conditional(
+                [Condition::new(
+                    Query::and([
+                        Query::metadata("user_plan").eq("paid"),
+                        Query::params("model").is_in(["gpt-4o", "gpt-4-turbo"]),
+                    ]),
+                    "premium",
+                )],
+                "standard",
+            ))
+            .target(Target::new().name("premium").virtual_key("openai-gpt-4o"))
+            .target(
+                Target::new()
+                    .name("standard")
+                    .virtual_key("openai-gpt-4o-mini"),
+            );
+
+        assert_eq!(
+            header(config),
+            json!({
+                "strategy": {
+                    "mode": "conditional",
+                    "conditions": [{
+                        "query": {
+                            "$and": [
+                                { "metadata.user_plan": { "$eq": "paid" } },
+                                { "params.model": { "$in": ["gpt-4o", "gpt-4-turbo"] } }
+                            ]
+                        },
+                        "then": "premium"
+                    }],
+                    "default": "standard"
+                },
+                "targets": [
+                    { "name": "premium", "virtual_key": "openai-gpt-4o" },
+                    { "name": "standard", "virtual_key": "openai-gpt-4o-mini" }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn debug_redacts_target_api_key() {
+        let config = Config::new().target(
+            Target::new()
+                .provider("openai")
+                .api_key("sk-SECRET")
+                .target(Target::new().provider("openai").api_key("sk-NESTED")),
+        );
+
+        let debug = format!("{config:?}");
+        assert!(!debug.contains("sk-SECRET"));
+        assert!(!debug.contains("sk-NESTED"));
+        assert!(debug.contains("<redacted>"));
+    }
+
+    #[test]
+    fn invalid_config_is_not_sent() {
+        let config = Config::new()
+            .strategy(Strategy::LoadBalance)
+            .target(Target::new().virtual_key("key-a").weight(-1.0));
+
+        assert!(matches!(
+            ConfigSource::Inline(Box::new(config)).header_value(),
+            Err(Error::InvalidConfig(_))
+        ));
+    }
+}
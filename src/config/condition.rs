@@ -0,0 +1,238 @@
+//! Conditions for [`Strategy::Conditional`](super::Strategy::Conditional) routing.
+
+use serde::{ser::SerializeMap, Serialize, Serializer};
+use serde_json::Value;
+
+/// A routing rule: requests matching `query` are sent to the target named `then`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Condition {
+    /// Query the request is matched against.
+    query: Query,
+    /// Name of the target matching requests are routed to.
+    then: String,
+}
+
+impl Condition {
+    /// Creates a condition routing requests matching `query` to the target
+    /// named `then`.
+    pub fn new(query: Query, then: impl Into<String>) -> Self {
+        Self {
+            query,
+            then: then.into(),
+        }
+    }
+
+    /// Returns the name of the target matching requests are routed to.
+    pub fn then(&self) -> &str {
+        &self.then
+    }
+}
+
+/// A query over request metadata and parameters.
+///
+/// Queries are built from a [`Field`] and a comparison, and can be combined with
+/// [`Query::and`] and [`Query::or`].
+///
+/// # Examples
+///
+/// ```rust
+/// use portkey::config::Query;
+///
+/// let premium = Query::metadata("user_plan").eq("paid");
+/// let large_model = Query::params("model").is_in(["gpt-4o", "gpt-4-turbo"]);
+///
+/// let query = Query::and([premium, large_model]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Query {
+    /// Compares a single field.
+    Compare {
+        /// Field being compared.
+        field: Field,
+        /// Comparison applied to the field.
+        operator: Operator,
+    },
+    /// Matches when all queries match.
+    And(Vec<Query>),
+    /// Matches when any query matches.
+    Or(Vec<Query>),
+}
+
+impl Query {
+    /// Starts a query on a request metadata key.
+    pub fn metadata(key: impl Into<String>) -> Field {
+        Field::Metadata(key.into())
+    }
+
+    /// Starts a query on a request parameter, e.g. `model`.
+    pub fn params(key: impl Into<String>) -> Field {
+        Field::Params(key.into())
+    }
+
+    /// Matches when all `queries` match.
+    pub fn and(queries: impl IntoIterator<Item = Query>) -> Self {
+        Self::And(queries.into_iter().collect())
+    }
+
+    /// Matches when any of `queries` matches.
+    pub fn or(queries: impl IntoIterator<Item = Query>) -> Self {
+        Self::Or(queries.into_iter().collect())
+    }
+}
+
+impl Serialize for Query {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+        match self {
+            Self::Compare { field, operator } => map.serialize_entry(&field.path(), operator)?,
+            Self::And(queries) => map.serialize_entry("$and", queries)?,
+            Self::Or(queries) => map.serialize_entry("$or", queries)?,
+        }
+        map.end()
+    }
+}
+
+/// A request field a [`Query`] can compare.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Field {
+    /// A key of the request metadata, see `x-portkey-metadata`.
+    Metadata(String),
+    /// A parameter of the request body, e.g. `model`.
+    Params(String),
+}
+
+impl Field {
+    /// Returns the dotted path Portkey uses to address the field.
+    fn path(&self) -> String {
+        match self {
+            Self::Metadata(key) => format!("metadata.{key}"),
+            Self::Params(key) => format!("params.{key}"),
+        }
+    }
+
+    fn compare(self, operator: Operator) -> Query {
+        Query::Compare {
+            field: self,
+            operator,
+        }
+    }
+
+    /// Matches when the field equals `value`.
+    pub fn eq(self, value: impl Into<Value>) -> Query {
+        self.compare(Operator::Eq(value.into()))
+    }
+
+    /// Matches when the field does not equal `value`.
+    pub fn ne(self, value: impl Into<Value>) -> Query {
+        self.compare(Operator::Ne(value.into()))
+    }
+
+    /// Matches when the field is greater than `value`.
+    pub fn gt(self, value: impl Into<Value>) -> Query {
+        self.compare(Operator::Gt(value.into()))
+    }
+
+    /// Matches when the field is greater than or equal to `value`.
+    pub fn gte(self, value: impl Into<Value>) -> Query {
+        self.compare(Operator::Gte(value.into()))
+    }
+
+    /// Matches when the field is less than `value`.
+    pub fn lt(self, value: impl Into<Value>) -> Query {
+        self.compare(Operator::Lt(value.into()))
+    }
+
+    /// Matches when the field is less than or equal to `value`.
+    pub fn lte(self, value: impl Into<Value>) -> Query {
+        self.compare(Operator::Lte(value.into()))
+    }
+
+    /// Matches when the field equals one of `values`.
+    pub fn is_in<V: Into<Value>>(self, values: impl IntoIterator<Item = V>) -> Query {
+        self.compare(Operator::In(values.into_iter().map(Into::into).collect()))
+    }
+
+    /// Matches when the field equals none of `values`.
+    pub fn not_in<V: Into<Value>>(self, values: impl IntoIterator<Item = V>) -> Query {
+        self.compare(Operator::NotIn(
+            values.into_iter().map(Into::into).collect(),
+        ))
+    }
+
+    /// Matches when the field matches the regular expression `pattern`.
+    pub fn regex(self, pattern: impl Into<String>) -> Query {
+        self.compare(Operator::Regex(pattern.into()))
+    }
+}
+
+/// A comparison applied to a [`Field`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[non_exhaustive]
+pub enum Operator {
+    /// Equal to.
+    #[serde(rename = "$eq")]
+    Eq(Value),
+    /// Not equal to.
+    #[serde(rename = "$ne")]
+    Ne(Value),
+    /// Greater than.
+    #[serde(rename = "$gt")]
+    Gt(Value),
+    /// Greater than or equal to.
+    #[serde(rename = "$gte")]
+    Gte(Value),
+    /// Less than.
+    #[serde(rename = "$lt")]
+    Lt(Value),
+    /// Less than or equal to.
+    #[serde(rename = "$lte")]
+    Lte(Value),
+    /// Equal to one of the values.
+    #[serde(rename = "$in")]
+    In(Vec<Value>),
+    /// Equal to none of the values.
+    #[serde(rename = "$nin")]
+    NotIn(Vec<Value>),
+    /// Matches a regular expression.
+    #[serde(rename = "$regex")]
+    Regex(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn query_operators() {
+        let query = Query::or([
+            Query::metadata("tier").ne("free"),
+            Query::params("max_tokens").gte(1000),
+            Query::params("model").not_in(["gpt-3.5-turbo"]),
+            Query::and([
+                Query::metadata("region").regex("^eu-"),
+                Query::params("temperature").lt(0.5),
+            ]),
+        ]);
+
+        assert_eq!(
+            serde_json::to_value(query).unwrap(),
+            json!({
+                "$or": [
+                    { "metadata.tier": { "$ne": "free" } },
+                    { "params.max_tokens": { "$gte": 1000 } },
+                    { "params.model": { "$nin": ["gpt-3.5-turbo"] } },
+                    {
+                        "$and": [
+                            { "metadata.region": { "$regex": "^eu-" } },
+                            { "params.temperature": { "$lt": 0.5 } }
+                        ]
+                    }
+                ]
+            })
+        );
+    }
+}
@@ -0,0 +1,233 @@
+//! Short-lived provider credentials that are refreshed before they expire.
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime},
+};
+
+/// How long before expiry cached credentials are refreshed.
+const REFRESH_MARGIN: Duration = Duration::from_secs(5 * 60);
+
+/// Credentials with an optional expiry time.
+pub trait Expiring {
+    /// Returns when the credentials expire, or `None` if they do not.
+    fn expires_at(&self) -> Option<SystemTime>;
+}
+
+/// A source of credentials that caches the last value and fetches new
+/// credentials shortly before the cached ones expire.
+///
+/// The fetch closure is called synchronously while a request is prepared, so
+/// it should return quickly, e.g. by reading credentials maintained by another
+/// component of the application.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::{Duration, SystemTime};
+///
+/// use portkey::{AwsCredentials, CredentialProvider};
+///
+/// let provider = CredentialProvider::new(|| {
+///     AwsCredentials::new("AKIA...", "secret")
+///         .session_token("session-token")
+///         .expires_at(SystemTime::now() + Duration::from_secs(3600))
+/// });
+/// ```
+pub struct CredentialProvider<T> {
+    /// Fetches fresh credentials.
+    fetch: Arc<dyn Fn() -> T + Send + Sync>,
+    /// The most recently fetched credentials.
+    cached: Arc<Mutex<Option<T>>>,
+}
+
+impl<T: Expiring + Clone> CredentialProvider<T> {
+    /// Creates a provider that calls `fetch` whenever fresh credentials are
+    /// needed.
+    pub fn new(fetch: impl Fn() -> T + Send + Sync + 'static) -> Self {
+        Self {
+            fetch: Arc::new(fetch),
+            cached: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Returns the cached credentials, fetching new ones if none are cached or
+    /// the cached ones expire within the refresh margin.
+    pub(crate) fn get(&self) -> T {
+        let mut cached = self
+            .cached
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        match cached.as_ref() {
+            Some(credentials) if !needs_refresh(credentials) => credentials.clone(),
+            _ => {
+                let credentials = (self.fetch)();
+                *cached = Some(credentials.clone());
+                credentials
+            }
+        }
+    }
+}
+
+impl<T> Clone for CredentialProvider<T> {
+    fn clone(&self) -> Self {
+        Self {
+            fetch: Arc::clone(&self.fetch),
+            cached: Arc::clone(&self.cached),
+        }
+    }
+}
+
+impl<T> fmt::Debug for CredentialProvider<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CredentialProvider").finish_non_exhaustive()
+    }
+}
+
+/// Returns `true` if `credentials` expire within [`REFRESH_MARGIN`].
+fn needs_refresh(credentials: &impl Expiring) -> bool {
+    credentials
+        .expires_at()
+        .is_some_and(|expires_at| expires_at <= SystemTime::now() + REFRESH_MARGIN)
+}
+
+/// AWS credentials used to sign Bedrock requests.
+#[derive(Clone, PartialEq, Eq)]
+pub struct AwsCredentials {
+    /// AWS access key ID.
+    pub(crate) access_key_id: String,
+    /// AWS secret access key.
+    pub(crate) secret_access_key: String,
+    /// Session token of temporary credentials.
+    pub(crate) session_token: Option<String>,
+    /// When temporary credentials expire.
+    expires_at: Option<SystemTime>,
+}
+
+impl AwsCredentials {
+    /// Creates long-lived credentials from an access key pair.
+    pub fn new(access_key_id: impl Into<String>, secret_access_key: impl Into<String>) -> Self {
+        Self {
+            access_key_id: access_key_id.into(),
+            secret_access_key: secret_access_key.into(),
+            session_token: None,
+            expires_at: None,
+        }
+    }
+
+    /// Sets the session token of temporary credentials.
+    pub fn session_token(mut self, session_token: impl Into<String>) -> Self {
+        self.session_token = Some(session_token.into());
+        self
+    }
+
+    /// Sets when temporary credentials expire.
+    pub fn expires_at(mut self, expires_at: SystemTime) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+}
+
+impl Expiring for AwsCredentials {
+    fn expires_at(&self) -> Option<SystemTime> {
+        self.expires_at
+    }
+}
+
+impl fmt::Debug for AwsCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AwsCredentials")
+            .field("access_key_id", &self.access_key_id)
+            .field("secret_access_key", &"<redacted>")
+            .field(
+                "session_token",
+                &self.session_token.as_ref().map(|_| "<redacted>"),
+            )
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
+}
+
+/// An OAuth access token, e.g. for Google Vertex AI.
+#[derive(Clone, PartialEq, Eq)]
+pub struct AccessToken {
+    /// The bearer token.
+    pub(crate) token: String,
+    /// When the token expires.
+    expires_at: Option<SystemTime>,
+}
+
+impl AccessToken {
+    /// Creates an access token without a known expiry.
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            token: token.into(),
+            expires_at: None,
+        }
+    }
+
+    /// Sets when the token expires.
+    pub fn expires_at(mut self, expires_at: SystemTime) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+}
+
+impl Expiring for AccessToken {
+    fn expires_at(&self) -> Option<SystemTime> {
+        self.expires_at
+    }
+}
+
+impl fmt::Debug for AccessToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AccessToken")
+            .field("token", &"<redacted>")
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    /// Returns a provider of tokens expiring after `lifetime`, and the number
+    /// of fetches it made.
+    fn provider(lifetime: Option<Duration>) -> (CredentialProvider<AccessToken>, Arc<AtomicUsize>) {
+        let fetches = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&fetches);
+        let provider = CredentialProvider::new(move || {
+            let fetch = counter.fetch_add(1, Ordering::SeqCst) + 1;
+            let token = AccessToken::new(format!("token-{fetch}"));
+            match lifetime {
+                Some(lifetime) => token.expires_at(SystemTime::now() + lifetime),
+                None => token,
+            }
+        });
+        (provider, fetches)
+    }
+
+    #[test]
+    fn reuses_credentials_outside_refresh_margin() {
+        for lifetime in [Some(REFRESH_MARGIN + Duration::from_secs(60)), None] {
+            let (provider, fetches) = provider(lifetime);
+
+            assert_eq!(provider.get().token, "token-1");
+            assert_eq!(provider.clone().get().token, "token-1");
+            assert_eq!(fetches.load(Ordering::SeqCst), 1);
+        }
+    }
+
+    #[test]
+    fn refetches_credentials_inside_refresh_margin() {
+        let (provider, fetches) = provider(Some(REFRESH_MARGIN - Duration::from_secs(60)));
+
+        assert_eq!(provider.get().token, "token-1");
+        assert_eq!(provider.get().token, "token-2");
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,37 @@
+//! Embeddings sent through the Portkey gateway.
+
+use async_openai::types::{CreateEmbeddingRequest, CreateEmbeddingResponse};
+
+use crate::{Client, PortkeyResponse, Result};
+
+/// Embeddings API.
+///
+/// Errors are returned as [`Error::Api`](crate::Error::Api) with the gateway's
+/// status, headers and error body.
+#[derive(Debug, Clone, Copy)]
+pub struct Embeddings<'c> {
+    client: &'c Client,
+}
+
+impl<'c> Embeddings<'c> {
+    pub(crate) fn new(client: &'c Client) -> Self {
+        Self { client }
+    }
+
+    /// Creates embeddings for the given input.
+    ///
+    /// The response carries Portkey's response metadata, see
+    /// [`PortkeyResponse::meta`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Api`](crate::Error::Api) if the gateway returns an
+    /// error response and [`Error::Request`](crate::Error::Request) if the
+    /// request fails.
+    pub async fn create(
+        &self,
+        request: CreateEmbeddingRequest,
+    ) -> Result<PortkeyResponse<CreateEmbeddingResponse>> {
+        self.client.post("/embeddings", &request).await
+    }
+}
@@ -0,0 +1,350 @@
+//! Error types returned by the Portkey SDK.
+
+use std::fmt;
+
+use async_openai::error::OpenAIError;
+use reqwest::{
+    header::{HeaderMap, InvalidHeaderName, InvalidHeaderValue},
+    StatusCode,
+};
+use serde_json::Value;
+
+use crate::headers;
+
+/// A specialized `Result` type for Portkey operations.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Errors that can occur while configuring or using a [`Client`](crate::Client).
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// A name could not be used as an HTTP header name.
+    #[error("invalid header name `{name}`")]
+    InvalidHeaderName {
+        /// The invalid header name.
+        name: String,
+        /// The underlying parse error.
+        #[source]
+        source: InvalidHeaderName,
+    },
+
+    /// A value could not be used as an HTTP header.
+    #[error("invalid value for header `{name}`")]
+    InvalidHeaderValue {
+        /// Name of the header the value was meant for.
+        name: String,
+        /// The underlying parse error.
+        #[source]
+        source: InvalidHeaderValue,
+    },
+
+    /// The configured base URL could not be parsed or is not an `http` or
+    /// `https` URL with a host.
+    #[error("invalid URL `{url}`")]
+    InvalidUrl {
+        /// The invalid URL.
+        url: String,
+        /// The underlying parse error, if the URL could not be parsed.
+        #[source]
+        source: Option<url::ParseError>,
+    },
+
+    /// The underlying HTTP client could not be constructed.
+    #[error("failed to build HTTP client")]
+    HttpClient(#[source] reqwest::Error),
+
+    /// The gateway config is invalid.
+    #[error("invalid gateway config: {0}")]
+    InvalidConfig(String),
+
+    /// The provider options conflict with other client settings.
+    #[error("invalid provider options: {0}")]
+    InvalidProviderOptions(String),
+
+    /// The request metadata exceeds Portkey's limits.
+    #[error("invalid metadata: {0}")]
+    InvalidMetadata(String),
+
+    /// An argument passed to an API call is not supported.
+    #[error("invalid argument: {0}")]
+    InvalidArgument(String),
+
+    /// The request could not be sent or the response could not be read.
+    #[error("request failed")]
+    Request(#[source] reqwest::Error),
+
+    /// A log export is in a status that [`Logs::wait_for_export`](crate::Logs::wait_for_export)
+    /// cannot wait on, e.g. because it was never started.
+    #[error("log export `{export_id}` cannot be awaited in status {status:?}")]
+    ExportNotRunning {
+        /// ID of the export.
+        export_id: String,
+        /// Status of the export.
+        status: crate::LogExportStatus,
+    },
+
+    /// A stream of server-sent events was malformed.
+    #[error("invalid event stream: {0}")]
+    Stream(String),
+
+    /// The request could not be sent or the response could not be read on any
+    /// of the attempts made under the client's
+    /// [`RetryPolicy`](crate::RetryPolicy), and its maximum number of retries
+    /// was reached.
+    ///
+    /// Transport failures that the policy does not retry, e.g. a timed out
+    /// `POST`, are returned as [`Error::Request`], even after earlier retries.
+    #[error("request failed after {attempts} attempts")]
+    RetriesExhausted {
+        /// Number of times the request was sent.
+        attempts: u32,
+        /// The error of the last attempt.
+        #[source]
+        source: reqwest::Error,
+    },
+
+    /// A middleware of the stack set with
+    /// [`ClientBuilder::middleware`](crate::ClientBuilder::middleware) failed.
+    #[cfg(feature = "middleware")]
+    #[error("middleware failed")]
+    Middleware(#[source] reqwest_middleware::Error),
+
+    /// The gateway or the upstream provider returned an error response.
+    #[error(transparent)]
+    Api(Box<ApiError>),
+
+    /// An error returned by the [`async_openai`] client from
+    /// [`Client::openai`](crate::Client::openai).
+    #[error(transparent)]
+    OpenAI(#[from] OpenAIError),
+
+    /// A file could not be read.
+    #[error("I/O error")]
+    Io(#[from] std::io::Error),
+
+    /// A value could not be serialized to or deserialized from JSON.
+    #[error("JSON error")]
+    Json(#[from] serde_json::Error),
+}
+
+/// An error response returned by the Portkey gateway.
+///
+/// Errors may originate from the gateway itself, e.g. an invalid config, or
+/// from the upstream provider a request was routed to. In both cases the
+/// gateway's error JSON, the HTTP status and the response headers are kept.
+#[derive(Debug, Clone)]
+pub struct ApiError {
+    /// HTTP status of the response.
+    status: StatusCode,
+    /// Human-readable error message.
+    message: String,
+    /// Error type reported by the provider, e.g. `invalid_request_error`.
+    error_type: Option<String>,
+    /// Error code reported by the provider.
+    code: Option<String>,
+    /// Provider that produced the error.
+    provider: Option<String>,
+    /// The full error body, or the raw body as a string if it is not JSON.
+    body: Value,
+    /// Response headers, including Portkey's `x-portkey-*` headers.
+    headers: HeaderMap,
+    /// Number of times the SDK sent the request.
+    attempts: u32,
+}
+
+impl ApiError {
+    /// Parses an error response.
+    pub(crate) fn from_response(status: StatusCode, headers: HeaderMap, body: &[u8]) -> Self {
+        let body = serde_json::from_slice(body)
+            .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(body).into_owned()));
+        let field = |pointers: &[&str]| {
+            pointers
+                .iter()
+                .find_map(|pointer| body.pointer(pointer))
+                .and_then(|value| match value {
+                    Value::String(value) => Some(value.clone()),
+                    Value::Number(value) => Some(value.to_string()),
+                    _ => None,
+                })
+        };
+
+        let message = field(&["/error/message", "/message"])
+            .or_else(|| {
+                body.as_str()
+                    .filter(|raw| !raw.is_empty())
+                    .map(str::to_owned)
+            })
+            .unwrap_or_else(|| status.to_string());
+        let error_type = field(&["/error/type"]);
+        let code = field(&["/error/code"]);
+        let provider = headers
+            .get(headers::PROVIDER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_owned)
+            .or_else(|| field(&["/provider", "/error/provider"]));
+
+        Self {
+            status,
+            message,
+            error_type,
+            code,
+            provider,
+            body,
+            headers,
+            attempts: 1,
+        }
+    }
+
+    /// Sets the number of times the SDK sent the request.
+    pub(crate) fn with_attempts(mut self, attempts: u32) -> Self {
+        self.attempts = attempts;
+        self
+    }
+
+    /// Returns the HTTP status of the response.
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// Returns the error message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the error type reported by the provider, if any.
+    pub fn error_type(&self) -> Option<&str> {
+        self.error_type.as_deref()
+    }
+
+    /// Returns the error code reported by the provider, if any.
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+
+    /// Returns the provider that produced the error, if known.
+    pub fn provider(&self) -> Option<&str> {
+        self.provider.as_deref()
+    }
+
+    /// Returns the index of the config target that served the request, e.g.
+    /// the last fallback target tried, as reported by
+    /// `x-portkey-last-used-option-index`.
+    pub fn last_used_option_index(&self) -> Option<&str> {
+        self.headers
+            .get(headers::LAST_USED_OPTION_INDEX)
+            .and_then(|value| value.to_str().ok())
+    }
+
+    /// Returns the error body as returned by the gateway.
+    ///
+    /// Bodies that are not JSON are returned as a JSON string.
+    pub fn body(&self) -> &Value {
+        &self.body
+    }
+
+    /// Returns the response headers.
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Returns the number of times the SDK sent the request, including
+    /// client-side retries made under a [`RetryPolicy`](crate::RetryPolicy).
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Portkey API error ({})", self.status)?;
+        if let Some(provider) = &self.provider {
+            write!(f, " from {provider}")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::HeaderValue;
+    use serde_json::json;
+
+    use super::*;
+
+    fn parse(status: u16, headers: HeaderMap, body: &[u8]) -> ApiError {
+        ApiError::from_response(StatusCode::from_u16(status).unwrap(), headers, body)
+    }
+
+    #[test]
+    fn parses_openai_error_body() {
+        let body = br#"{
+            "error": {
+                "message": "Rate limit reached",
+                "type": "rate_limit_error",
+                "code": "rate_limit_exceeded"
+            },
+            "provider": "openai"
+        }"#;
+        let error = parse(429, HeaderMap::new(), body);
+
+        assert_eq!(error.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(error.message(), "Rate limit reached");
+        assert_eq!(error.error_type(), Some("rate_limit_error"));
+        assert_eq!(error.code(), Some("rate_limit_exceeded"));
+        assert_eq!(error.provider(), Some("openai"));
+        assert_eq!(error.body()["error"]["type"], "rate_limit_error");
+        assert_eq!(error.attempts(), 1);
+        assert_eq!(
+            error.to_string(),
+            "Portkey API error (429 Too Many Requests) from openai: Rate limit reached"
+        );
+    }
+
+    #[test]
+    fn falls_back_to_top_level_message_and_numeric_code() {
+        let body = br#"{ "message": "Invalid config", "error": { "code": 400 } }"#;
+        let error = parse(400, HeaderMap::new(), body);
+
+        assert_eq!(error.message(), "Invalid config");
+        assert_eq!(error.code(), Some("400"));
+        assert_eq!(error.error_type(), None);
+        assert_eq!(error.provider(), None);
+    }
+
+    #[test]
+    fn prefers_provider_header_over_body() {
+        let mut headers = HeaderMap::new();
+        headers.insert(headers::PROVIDER, HeaderValue::from_static("anthropic"));
+        headers.insert(
+            headers::LAST_USED_OPTION_INDEX,
+            HeaderValue::from_static("config.targets[1]"),
+        );
+        let body = br#"{ "error": { "message": "Overloaded", "provider": "bedrock" } }"#;
+        let error = parse(529, headers, body);
+
+        assert_eq!(error.provider(), Some("anthropic"));
+        assert_eq!(error.last_used_option_index(), Some("config.targets[1]"));
+
+        let error = parse(529, HeaderMap::new(), body);
+        assert_eq!(error.provider(), Some("bedrock"));
+    }
+
+    #[test]
+    fn keeps_raw_body_that_is_not_json() {
+        let error = parse(502, HeaderMap::new(), b"<html>Bad Gateway</html>");
+
+        assert_eq!(error.message(), "<html>Bad Gateway</html>");
+        assert_eq!(error.body(), &json!("<html>Bad Gateway</html>"));
+    }
+
+    #[test]
+    fn falls_back_to_status_for_empty_body() {
+        let error = parse(503, HeaderMap::new(), b"");
+
+        assert_eq!(error.message(), "503 Service Unavailable");
+        assert_eq!(error.body(), &json!(""));
+        assert_eq!(error.code(), None);
+    }
+}
@@ -0,0 +1,236 @@
+//! Feedback on requests logged by Portkey.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{http::path, Client, Error, Metadata, PortkeyResponse, Result};
+
+/// Range of feedback values accepted by Portkey.
+const VALUE_RANGE: std::ops::RangeInclusive<i32> = -10..=10;
+
+/// Feedback API.
+///
+/// Feedback is attached to the requests sharing its trace ID, see
+/// [`ClientBuilder::trace_id`](crate::ClientBuilder::trace_id) and
+/// [`RequestOptions::trace_id`](crate::RequestOptions::trace_id).
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use portkey::{Client, Feedback, Metadata};
+///
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::new("your-portkey-api-key", "your-portkey-virtual-key");
+///
+/// let feedback = Feedback::new("trace-123", 1).metadata(Metadata::new().user("user-123"));
+/// let response = client.feedback().create(&feedback).await?;
+///
+/// // The user changed their mind.
+/// let feedback = Feedback::new("trace-123", -1);
+/// for id in &response.feedback_ids {
+///     client.feedback().update(id, &feedback).await?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct FeedbackApi<'c> {
+    client: &'c Client,
+}
+
+impl<'c> FeedbackApi<'c> {
+    pub(crate) fn new(client: &'c Client) -> Self {
+        Self { client }
+    }
+
+    /// Creates feedback for the requests with the feedback's trace ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] or [`Error::InvalidMetadata`] if the
+    /// feedback is invalid, [`Error::Api`] if the gateway returns an error
+    /// response and [`Error::Request`] if the request fails.
+    pub async fn create(&self, feedback: &Feedback) -> Result<PortkeyResponse<FeedbackResponse>> {
+        feedback.validate()?;
+        self.client.post("/feedback", feedback).await
+    }
+
+    /// Replaces the value, weight and metadata of the feedback `feedback_id`.
+    ///
+    /// The trace ID of existing feedback cannot be changed, so the trace ID of
+    /// `feedback` is not sent.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`FeedbackApi::create`], including [`Error::InvalidArgument`]
+    /// if `feedback_id` is empty, `.` or `..`.
+    pub async fn update(
+        &self,
+        feedback_id: &str,
+        feedback: &Feedback,
+    ) -> Result<PortkeyResponse<FeedbackResponse>> {
+        feedback.validate()?;
+        self.client
+            .put(
+                &path(&["feedback", feedback_id])?,
+                &FeedbackUpdate::from(feedback),
+            )
+            .await
+    }
+}
+
+/// Feedback on the requests sharing a trace ID.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Feedback {
+    /// Trace ID of the rated requests.
+    trace_id: String,
+    /// Rating between -10 and 10.
+    value: i32,
+    /// Weight of the rating between 0 and 1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    weight: Option<f32>,
+    /// Metadata for filtering feedback.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metadata: Option<Metadata>,
+}
+
+impl Feedback {
+    /// Creates feedback with a rating between -10 and 10, e.g. `1` and `-1`
+    /// for thumbs up and down.
+    pub fn new(trace_id: impl Into<String>, value: i32) -> Self {
+        Self {
+            trace_id: trace_id.into(),
+            value,
+            weight: None,
+            metadata: None,
+        }
+    }
+
+    /// Sets the weight of the rating between 0 and 1. Portkey defaults to `1`.
+    pub fn weight(mut self, weight: f32) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
+    /// Sets metadata for filtering feedback.
+    pub fn metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// Checks the feedback against Portkey's limits.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if the trace ID is empty or the value
+    /// or weight is out of range, and [`Error::InvalidMetadata`] if the
+    /// metadata is invalid.
+    pub fn validate(&self) -> Result<()> {
+        if self.trace_id.is_empty() {
+            return Err(Error::InvalidArgument(
+                "feedback trace ID must not be empty".to_string(),
+            ));
+        }
+        if !VALUE_RANGE.contains(&self.value) {
+            return Err(Error::InvalidArgument(format!(
+                "feedback value must be between {} and {}, got {}",
+                VALUE_RANGE.start(),
+                VALUE_RANGE.end(),
+                self.value
+            )));
+        }
+        if let Some(weight) = self.weight {
+            if !(0.0..=1.0).contains(&weight) {
+                return Err(Error::InvalidArgument(format!(
+                    "feedback weight must be between 0 and 1, got {weight}"
+                )));
+            }
+        }
+        if let Some(metadata) = &self.metadata {
+            metadata.validate()?;
+        }
+        Ok(())
+    }
+}
+
+/// Body of feedback updates.
+#[derive(Serialize)]
+struct FeedbackUpdate<'f> {
+    value: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    weight: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metadata: Option<&'f Metadata>,
+}
+
+impl<'f> From<&'f Feedback> for FeedbackUpdate<'f> {
+    fn from(feedback: &'f Feedback) -> Self {
+        Self {
+            value: feedback.value,
+            weight: feedback.weight,
+            metadata: feedback.metadata.as_ref(),
+        }
+    }
+}
+
+/// Response to a feedback request.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct FeedbackResponse {
+    /// Status of the request, e.g. `success`.
+    pub status: String,
+    /// Message describing the outcome.
+    #[serde(default)]
+    pub message: String,
+    /// IDs of the created or updated feedback.
+    #[serde(default)]
+    pub feedback_ids: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn validates_feedback() {
+        assert!(Feedback::new("trace-1", 10).weight(0.0).validate().is_ok());
+        assert!(Feedback::new("trace-1", -10).weight(1.0).validate().is_ok());
+        for feedback in [
+            Feedback::new("", 1),
+            Feedback::new("trace-1", 11),
+            Feedback::new("trace-1", -11),
+            Feedback::new("trace-1", 1).weight(1.5),
+            Feedback::new("trace-1", 1).weight(-0.1),
+        ] {
+            assert!(
+                matches!(feedback.validate(), Err(Error::InvalidArgument(_))),
+                "{feedback:?} was accepted"
+            );
+        }
+    }
+
+    #[test]
+    fn serializes_create_and_update_bodies() {
+        let feedback = Feedback::new("trace-1", -1)
+            .weight(0.5)
+            .metadata(Metadata::new().user("user-123"));
+
+        assert_eq!(
+            serde_json::to_value(&feedback).unwrap(),
+            json!({
+                "trace_id": "trace-1",
+                "value": -1,
+                "weight": 0.5,
+                "metadata": { "_user": "user-123" }
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(FeedbackUpdate::from(&feedback)).unwrap(),
+            json!({ "value": -1, "weight": 0.5, "metadata": { "_user": "user-123" } })
+        );
+        assert_eq!(
+            serde_json::to_value(FeedbackUpdate::from(&Feedback::new("trace-1", 1))).unwrap(),
+            json!({ "value": 1 })
+        );
+    }
+}
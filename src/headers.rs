@@ -0,0 +1,115 @@
+//! Names of the headers understood by the Portkey gateway.
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+
+use crate::{Error, Result};
+
+/// Portkey API key.
+pub(crate) const API_KEY: &str = "x-portkey-api-key";
+/// Name of the upstream provider, used with the provider's own API key.
+pub(crate) const PROVIDER: &str = "x-portkey-provider";
+/// Portkey virtual key of the provider.
+pub(crate) const VIRTUAL_KEY: &str = "x-portkey-virtual-key";
+/// Gateway config, inline JSON or a saved config ID.
+pub(crate) const CONFIG: &str = "x-portkey-config";
+/// Name of the Azure OpenAI resource.
+pub(crate) const AZURE_RESOURCE_NAME: &str = "x-portkey-azure-resource-name";
+/// ID of the Azure OpenAI model deployment.
+pub(crate) const AZURE_DEPLOYMENT_ID: &str = "x-portkey-azure-deployment-id";
+/// Azure OpenAI API version.
+pub(crate) const AZURE_API_VERSION: &str = "x-portkey-azure-api-version";
+/// Name of the model behind an Azure OpenAI deployment.
+pub(crate) const AZURE_MODEL_NAME: &str = "x-portkey-azure-model-name";
+/// AWS access key ID for Bedrock.
+pub(crate) const AWS_ACCESS_KEY_ID: &str = "x-portkey-aws-access-key-id";
+/// AWS secret access key for Bedrock.
+pub(crate) const AWS_SECRET_ACCESS_KEY: &str = "x-portkey-aws-secret-access-key";
+/// AWS session token of temporary Bedrock credentials.
+pub(crate) const AWS_SESSION_TOKEN: &str = "x-portkey-aws-session-token";
+/// AWS region of the Bedrock endpoint.
+pub(crate) const AWS_REGION: &str = "x-portkey-aws-region";
+/// Google Cloud project of the Vertex AI endpoint.
+pub(crate) const VERTEX_PROJECT_ID: &str = "x-portkey-vertex-project-id";
+/// Google Cloud region of the Vertex AI endpoint.
+pub(crate) const VERTEX_REGION: &str = "x-portkey-vertex-region";
+/// Service account key the gateway derives Vertex AI tokens from.
+pub(crate) const VERTEX_SERVICE_ACCOUNT_JSON: &str = "x-portkey-vertex-service-account-json";
+/// Trace ID correlating a request with Portkey's logs.
+pub(crate) const TRACE_ID: &str = "x-portkey-trace-id";
+/// Request metadata as a JSON object.
+pub(crate) const METADATA: &str = "x-portkey-metadata";
+/// Config target that served a request, e.g. `config.targets[1]`.
+pub(crate) const LAST_USED_OPTION_INDEX: &str = "x-portkey-last-used-option-index";
+/// Whether a response was served from Portkey's cache.
+pub(crate) const CACHE_STATUS: &str = "x-portkey-cache-status";
+/// Number of retries the gateway made for a request.
+pub(crate) const RETRY_ATTEMPT_COUNT: &str = "x-portkey-retry-attempt-count";
+/// Forces the gateway to refresh a cached response.
+pub(crate) const CACHE_FORCE_REFRESH: &str = "x-portkey-cache-force-refresh";
+
+/// Headers carrying credentials, hidden from `Debug` output. Inline gateway
+/// configs may contain provider API keys.
+const SENSITIVE: &[&str] = &[
+    "authorization",
+    API_KEY,
+    CONFIG,
+    AWS_ACCESS_KEY_ID,
+    AWS_SECRET_ACCESS_KEY,
+    AWS_SESSION_TOKEN,
+    VERTEX_SERVICE_ACCOUNT_JSON,
+];
+
+/// Inserts a header, replacing any previous value.
+///
+/// Credential headers are marked sensitive so that they are not printed by
+/// `Debug`. Maps invalid names and values to [`Error::InvalidHeaderName`] and
+/// [`Error::InvalidHeaderValue`].
+pub(crate) fn insert(headers: &mut HeaderMap, name: &str, value: &str) -> Result<()> {
+    let header_name =
+        HeaderName::from_bytes(name.as_bytes()).map_err(|source| Error::InvalidHeaderName {
+            name: name.to_string(),
+            source,
+        })?;
+    let mut header_value =
+        HeaderValue::from_str(value).map_err(|source| Error::InvalidHeaderValue {
+            name: name.to_string(),
+            source,
+        })?;
+    header_value.set_sensitive(SENSITIVE.contains(&header_name.as_str()));
+    headers.insert(header_name, header_value);
+    Ok(())
+}
+
+/// Generates a new random trace ID.
+pub(crate) fn generate_trace_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    /// Asserts that `headers` holds exactly the `expected` names and values.
+    pub(crate) fn assert_headers(headers: &HeaderMap, expected: &[(&str, &str)]) {
+        let actual: BTreeMap<_, _> = headers
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.to_str().unwrap()))
+            .collect();
+        assert_eq!(actual, expected.iter().copied().collect());
+    }
+
+    #[test]
+    fn marks_credential_headers_sensitive() {
+        let mut headers = HeaderMap::new();
+        insert(&mut headers, "Authorization", "Bearer sk-SECRET").unwrap();
+        insert(&mut headers, CONFIG, r#"{"api_key":"sk-SECRET"}"#).unwrap();
+        insert(&mut headers, TRACE_ID, "trace-1").unwrap();
+
+        assert!(headers["authorization"].is_sensitive());
+        assert!(headers[CONFIG].is_sensitive());
+        assert!(!headers[TRACE_ID].is_sensitive());
+        assert!(!format!("{headers:?}").contains("sk-SECRET"));
+    }
+}
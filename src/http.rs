@@ -0,0 +1,287 @@
+//! Requests sent by the SDK itself rather than through `async-openai`.
+
+use std::pin::Pin;
+
+use async_openai::config::Config as _;
+use eventsource_stream::{EventStreamError, Eventsource};
+use futures::{Stream, StreamExt};
+use reqwest::{header::CONTENT_TYPE, Method, Request, Response};
+use serde::{de::DeserializeOwned, Serialize};
+use url::Url;
+
+use crate::{error::ApiError, headers, Client, Error, PortkeyResponse, ResponseMeta, Result};
+
+/// A stream of server-sent events deserialized as `T`.
+pub type EventStream<T> = Pin<Box<dyn Stream<Item = Result<T>> + Send>>;
+
+/// Builds a path relative to the base URL from `segments`, percent-encoding
+/// each segment so that caller-supplied IDs cannot change the path or add a
+/// query.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidArgument`] for empty, `.` and `..` segments, which
+/// would address a different resource.
+pub(crate) fn path(segments: &[&str]) -> Result<String> {
+    if let Some(segment) = segments
+        .iter()
+        .find(|segment| matches!(**segment, "" | "." | ".."))
+    {
+        return Err(Error::InvalidArgument(format!(
+            "`{segment}` is not a valid path segment"
+        )));
+    }
+    let mut url = Url::parse("http://localhost").expect("valid URL");
+    url.path_segments_mut()
+        .expect("URL can be a base")
+        .extend(segments);
+    Ok(url.path().to_string())
+}
+
+/// A successful response together with its request's details.
+struct Sent {
+    /// The response, with a success status.
+    response: Response,
+    /// Trace ID sent with the request.
+    trace_id: Option<String>,
+    /// Number of times the request was sent.
+    attempts: u32,
+}
+
+impl Client {
+    /// Sends a `GET` request and deserializes the JSON response.
+    pub(crate) async fn get<O>(&self, path: &str) -> Result<PortkeyResponse<O>>
+    where
+        O: DeserializeOwned,
+    {
+        self.execute(Method::GET, path, None::<&()>).await
+    }
+
+    /// Sends a `POST` request with a JSON body and deserializes the JSON
+    /// response.
+    pub(crate) async fn post<I, O>(&self, path: &str, body: &I) -> Result<PortkeyResponse<O>>
+    where
+        I: Serialize + ?Sized,
+        O: DeserializeOwned,
+    {
+        self.execute(Method::POST, path, Some(body)).await
+    }
+
+    /// Sends a `PUT` request with a JSON body and deserializes the JSON
+    /// response.
+    pub(crate) async fn put<I, O>(&self, path: &str, body: &I) -> Result<PortkeyResponse<O>>
+    where
+        I: Serialize + ?Sized,
+        O: DeserializeOwned,
+    {
+        self.execute(Method::PUT, path, Some(body)).await
+    }
+
+    /// Sends a `POST` request with a JSON body and returns the response as a
+    /// stream of server-sent events.
+    pub(crate) async fn post_stream<I, O>(&self, path: &str, body: &I) -> Result<EventStream<O>>
+    where
+        I: Serialize + ?Sized,
+        O: DeserializeOwned + Send + 'static,
+    {
+        let sent = self.send_with_retry(Method::POST, path, Some(body)).await?;
+        let stream = sent
+            .response
+            .bytes_stream()
+            .eventsource()
+            .take_while(|event| {
+                let done = matches!(event, Ok(event) if event.data == "[DONE]");
+                futures::future::ready(!done)
+            })
+            .map(|event| match event {
+                Ok(event) => Ok(serde_json::from_str(&event.data)?),
+                Err(EventStreamError::Transport(error)) => Err(Error::Request(error)),
+                Err(error) => Err(Error::Stream(error.to_string())),
+            });
+        Ok(Box::pin(stream))
+    }
+
+    /// Downloads `url`, e.g. a signed URL returned by Portkey, without sending
+    /// Portkey's headers.
+    pub(crate) async fn download(&self, url: &str) -> Result<Vec<u8>> {
+        let request = self.http.get(url).build().map_err(Error::Request)?;
+        let response = self.send(request).await?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let bytes = response.bytes().await.map_err(Error::Request)?;
+
+        if !status.is_success() {
+            return Err(Error::Api(Box::new(ApiError::from_response(
+                status, headers, &bytes,
+            ))));
+        }
+        Ok(bytes.to_vec())
+    }
+
+    /// Sends a request with an optional JSON body and discards the response
+    /// body, which may be empty, e.g. for `204 No Content`.
+    pub(crate) async fn send_discarding<I>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&I>,
+    ) -> Result<ResponseMeta>
+    where
+        I: Serialize + ?Sized,
+    {
+        let Sent {
+            response,
+            trace_id,
+            attempts,
+        } = self.send_with_retry(method, path, body).await?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        // Read the body so that the connection can be reused.
+        response.bytes().await.map_err(Error::Request)?;
+
+        Ok(
+            ResponseMeta::from_headers(status, headers, trace_id.as_deref())
+                .with_attempts(attempts),
+        )
+    }
+
+    /// Sends a request to `path` relative to the base URL and deserializes the
+    /// JSON response together with Portkey's response headers.
+    async fn execute<I, O>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&I>,
+    ) -> Result<PortkeyResponse<O>>
+    where
+        I: Serialize + ?Sized,
+        O: DeserializeOwned,
+    {
+        let Sent {
+            response,
+            trace_id,
+            attempts,
+        } = self.send_with_retry(method, path, body).await?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let bytes = response.bytes().await.map_err(Error::Request)?;
+
+        let body = serde_json::from_slice(&bytes)?;
+        let meta = ResponseMeta::from_headers(status, headers, trace_id.as_deref())
+            .with_attempts(attempts);
+        Ok(PortkeyResponse::new(body, meta))
+    }
+
+    /// Sends a request to `path` relative to the base URL.
+    ///
+    /// Failed attempts are retried according to the client's
+    /// [`RetryPolicy`](crate::RetryPolicy). Non-success responses are returned
+    /// as [`Error::Api`].
+    async fn send_with_retry<I>(&self, method: Method, path: &str, body: Option<&I>) -> Result<Sent>
+    where
+        I: Serialize + ?Sized,
+    {
+        let request_headers = self.config.request_headers();
+        let trace_id = request_headers
+            .get(headers::TRACE_ID)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_owned);
+        let url = self.config.url(path);
+        let body = body.map(serde_json::to_vec).transpose()?;
+
+        let mut attempts = 1;
+        let response = loop {
+            let mut request = self
+                .http
+                .request(method.clone(), &url)
+                .headers(request_headers.clone());
+            if let Some(body) = &body {
+                request = request
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(body.clone());
+            }
+
+            let outcome = self.send(request.build().map_err(Error::Request)?).await;
+            let delay = self
+                .retry_policy
+                .and_then(|policy| policy.retry_delay(&method, &outcome, attempts));
+            match delay {
+                Some(delay) => {
+                    tokio::time::sleep(delay).await;
+                    attempts += 1;
+                }
+                None => {
+                    let exhausted = attempts > 1
+                        && self
+                            .retry_policy
+                            .is_some_and(|policy| policy.is_exhausted(&method, &outcome, attempts));
+                    break outcome.map_err(|error| match error {
+                        Error::Request(source) if exhausted => {
+                            Error::RetriesExhausted { attempts, source }
+                        }
+                        error => error,
+                    })?;
+                }
+            }
+        };
+
+        let status = response.status();
+        if !status.is_success() {
+            let headers = response.headers().clone();
+            let bytes = response.bytes().await.map_err(Error::Request)?;
+            return Err(Error::Api(Box::new(
+                ApiError::from_response(status, headers, &bytes).with_attempts(attempts),
+            )));
+        }
+
+        Ok(Sent {
+            response,
+            trace_id,
+            attempts,
+        })
+    }
+
+    /// Sends a single request, through the middleware stack if one is set.
+    async fn send(&self, request: Request) -> Result<Response> {
+        #[cfg(feature = "middleware")]
+        if let Some(middleware) = &self.middleware {
+            return middleware
+                .execute(request)
+                .await
+                .map_err(|error| match error {
+                    reqwest_middleware::Error::Reqwest(error) => Error::Request(error),
+                    error => Error::Middleware(error),
+                });
+        }
+
+        self.http.execute(request).await.map_err(Error::Request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_encodes_segments() {
+        assert_eq!(
+            path(&["feedback", "../../feedback?x=1 y"]).unwrap(),
+            "/feedback/..%2F..%2Ffeedback%3Fx=1%20y"
+        );
+        assert_eq!(
+            path(&["prompts", "pp-1@latest", "render"]).unwrap(),
+            "/prompts/pp-1@latest/render"
+        );
+        assert_eq!(path(&["logs", "a#b%c"]).unwrap(), "/logs/a%23b%25c");
+    }
+
+    #[test]
+    fn path_rejects_dot_and_empty_segments() {
+        for segment in ["", ".", ".."] {
+            assert!(matches!(
+                path(&["virtual-keys", segment]),
+                Err(Error::InvalidArgument(_))
+            ));
+        }
+    }
+}
@@ -8,12 +8,72 @@
 //! - Integrates with `async-openai` for OpenAI API compatibility.
 //! - Configures custom headers for Portkey-specific requirements.
 //! - Simplifies initialization with `api_key` and `virtual_key`.
+//! - Virtual key, provider key and saved config authentication through [`Auth`].
+//! - Azure OpenAI, AWS Bedrock and Google Vertex AI routing through [`ProviderOptions`].
+//! - Supports self-hosted gateways through [`ClientBuilder`].
+//! - Typed gateway configs through [`config::Config`].
+//! - Per-request header overrides through [`RequestOptions`].
+//! - Structured request metadata through [`Metadata`].
+//! - Cache status, trace ID, retry count and serving target of responses through
+//!   [`ResponseMeta`].
+//! - Opt-in client-side retries of transport failures through [`RetryPolicy`].
+//! - Bring-your-own `reqwest::Client` and, with the `middleware` feature, a
+//!   `reqwest-middleware` stack.
+//! - Rendering and completing saved prompt templates through [`Prompts`].
+//! - Feedback on logged requests through [`FeedbackApi`].
+//! - Log retrieval, bulk log exports and logging of calls made without the
+//!   gateway through [`Logs`].
+//! - Virtual key administration through [`admin::VirtualKeys`].
+//! - Portkey error details such as status, provider and error body through [`ApiError`].
 //!
 //! ## License
 //! This library is distributed under the MIT License. See the `LICENSE` file for details.
 
-use async_openai::{config::OpenAIConfig, Client as OpenAIClient};
-use reqwest::{header::HeaderMap, Client as ReqwestClient};
+pub mod admin;
+pub mod config;
+
+mod auth;
+mod builder;
+mod chat;
+mod credentials;
+mod embeddings;
+mod error;
+mod feedback;
+mod headers;
+mod http;
+mod logs;
+mod metadata;
+mod openai;
+mod options;
+mod prompts;
+mod provider;
+mod response;
+mod retry;
+
+pub use auth::Auth;
+pub use builder::ClientBuilder;
+pub use chat::Chat;
+pub use credentials::{AccessToken, AwsCredentials, CredentialProvider, Expiring};
+pub use embeddings::Embeddings;
+pub use error::{ApiError, Error, Result};
+pub use feedback::{Feedback, FeedbackApi, FeedbackResponse};
+pub use http::EventStream;
+pub use logs::{
+    CustomLog, LogEntry, LogExport, LogExportCreated, LogExportDownload, LogExportRequest,
+    LogExportStatus, LogFilter, LogPage, LogStream, LoggedRequest, LoggedResponse, Logs,
+};
+pub use metadata::Metadata;
+pub use openai::PortkeyConfig;
+pub use options::RequestOptions;
+pub use prompts::{PromptRef, PromptVersion, Prompts, RenderedPrompt};
+pub use provider::{AzureOptions, BedrockOptions, ProviderOptions, VertexOptions};
+pub use response::{CacheStatus, PortkeyResponse, ResponseMeta};
+pub use retry::RetryPolicy;
+
+use admin::Admin;
+use async_openai::Client as OpenAIClient;
+use config::ConfigSource;
+use reqwest::Client as ReqwestClient;
 
 /// Base URL for the Portkey AI API.
 const BASE_URL: &str = "https://api.portkey.ai/v1";
@@ -37,16 +97,27 @@ const BASE_URL: &str = "https://api.portkey.ai/v1";
 /// // Access the OpenAI client
 /// let openai_client = client.openai();
 /// ```
+#[derive(Debug, Clone)]
 pub struct Client {
-    /// OpenAI client configured for Portkey.
-    openai: OpenAIClient<OpenAIConfig>,
+    /// HTTP client shared by all requests.
+    http: ReqwestClient,
+    /// OpenAI configuration carrying the Portkey headers.
+    config: PortkeyConfig,
+    /// Gateway config sent with every request.
+    gateway_config: Option<ConfigSource>,
+    /// Client-side retries of failed requests.
+    retry_policy: Option<RetryPolicy>,
+    /// Middleware stack for requests sent by the SDK.
+    #[cfg(feature = "middleware")]
+    middleware: Option<reqwest_middleware::ClientWithMiddleware>,
 }
 
 impl Client {
     /// Creates a new instance of the `Client`.
     ///
     /// This method sets up the required headers and configures the OpenAI client
-    /// to work with the Portkey API.
+    /// to work with the hosted Portkey API. Use [`Client::builder`] for further
+    /// customization.
     ///
     /// # Arguments
     ///
@@ -57,6 +128,11 @@ impl Client {
     ///
     /// A configured instance of the `Client`.
     ///
+    /// # Panics
+    ///
+    /// Panics if the virtual key is not a valid header value. Use
+    /// [`Client::try_new`] to handle this case gracefully.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -68,23 +144,51 @@ impl Client {
     /// let client = Client::new(api_key, virtual_key);
     /// ```
     pub fn new(api_key: &str, virtual_key: &str) -> Self {
-        let mut reqwest_headers = HeaderMap::new();
-        reqwest_headers.insert(
-            "x-portkey-virtual-key",
-            virtual_key.parse().expect("Failed to parse virtual key"),
-        );
-        let reqwest_client = ReqwestClient::builder()
-            .default_headers(reqwest_headers)
-            .build()
-            .expect("Failed to build reqwest client");
-
-        let openai_config = OpenAIConfig::new()
-            .with_api_base(BASE_URL)
-            .with_api_key(api_key);
+        Self::try_new(api_key, virtual_key).expect("Failed to build Portkey client")
+    }
 
-        let openai = OpenAIClient::with_config(openai_config).with_http_client(reqwest_client);
+    /// Creates a new instance of the `Client`, returning an error instead of
+    /// panicking on invalid input.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidHeaderValue`] if the virtual key is not a valid
+    /// header value and [`Error::HttpClient`] if the HTTP client cannot be built.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use portkey::{Client, Error};
+    ///
+    /// let client = Client::try_new("your-portkey-api-key", "your-portkey-virtual-key");
+    /// assert!(client.is_ok());
+    ///
+    /// let invalid = Client::try_new("your-portkey-api-key", "invalid\nkey");
+    /// assert!(matches!(invalid, Err(Error::InvalidHeaderValue { .. })));
+    /// ```
+    pub fn try_new(api_key: &str, virtual_key: &str) -> Result<Self> {
+        Self::builder()
+            .api_key(api_key)
+            .virtual_key(virtual_key)
+            .build()
+    }
 
-        Self { openai }
+    /// Returns a [`ClientBuilder`] for configuring a custom `Client`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use portkey::Client;
+    ///
+    /// let client = Client::builder()
+    ///     .api_key("your-portkey-api-key")
+    ///     .virtual_key("your-portkey-virtual-key")
+    ///     .base_url("http://localhost:8787/v1")
+    ///     .build()
+    ///     .expect("valid Portkey configuration");
+    /// ```
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
     }
 
     /// Returns the underlying OpenAI client configured for Portkey.
@@ -103,7 +207,111 @@ impl Client {
     ///
     /// let openai_client = client.openai();
     /// ```
-    pub fn openai(self) -> OpenAIClient<OpenAIConfig> {
-        self.openai
+    pub fn openai(&self) -> OpenAIClient<PortkeyConfig> {
+        OpenAIClient::with_config(self.config.clone()).with_http_client(self.http.clone())
+    }
+
+    /// Returns the chat completions API.
+    ///
+    /// Errors include the gateway's status, headers and error body, see
+    /// [`ApiError`].
+    pub fn chat(&self) -> Chat<'_> {
+        Chat::new(self)
+    }
+
+    /// Returns the embeddings API.
+    ///
+    /// Errors include the gateway's status, headers and error body, see
+    /// [`ApiError`].
+    pub fn embeddings(&self) -> Embeddings<'_> {
+        Embeddings::new(self)
+    }
+
+    /// Returns the prompts API for templates saved in Portkey's prompt library.
+    pub fn prompts(&self) -> Prompts<'_> {
+        Prompts::new(self)
+    }
+
+    /// Returns the feedback API for rating requests by trace ID.
+    pub fn feedback(&self) -> FeedbackApi<'_> {
+        FeedbackApi::new(self)
+    }
+
+    /// Returns the logs API for retrieving, exporting and inserting request
+    /// logs.
+    pub fn logs(&self) -> Logs<'_> {
+        Logs::new(self)
+    }
+
+    /// Returns the admin APIs. They require a Portkey API key with admin
+    /// permissions.
+    pub fn admin(&self) -> Admin<'_> {
+        Admin::new(self)
+    }
+
+    /// Returns a client that applies `options` on top of this client's headers.
+    ///
+    /// The returned client shares this client's connection pool, so it is cheap
+    /// to create one for a single request.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if an option is not a valid header value or if an
+    /// inline gateway config or the metadata is invalid. Cache settings cannot
+    /// be added to a saved config. Returns [`Error::InvalidArgument`] if a
+    /// virtual key is set on a client that authenticates with
+    /// [`Auth::Provider`] or [`ProviderOptions`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use async_openai::types::CreateEmbeddingRequestArgs;
+    /// use portkey::{Client, RequestOptions};
+    ///
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("your-portkey-api-key", "your-portkey-virtual-key");
+    ///
+    /// let request = CreateEmbeddingRequestArgs::default()
+    ///     .model("text-embedding-3-small")
+    ///     .input("Hello, Portkey!")
+    ///     .build()?;
+    ///
+    /// let options = RequestOptions::new().virtual_key("another-virtual-key");
+    /// let response = client
+    ///     .with_options(&options)?
+    ///     .openai()
+    ///     .embeddings()
+    ///     .create(request)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_options(&self, options: &RequestOptions) -> Result<Self> {
+        let mut headers = options.header_map()?;
+        if headers.contains_key(headers::VIRTUAL_KEY) && self.config.has_header(headers::PROVIDER) {
+            return Err(Error::InvalidArgument(
+                "a virtual key cannot be combined with provider authentication".to_string(),
+            ));
+        }
+        let gateway_config = match options.gateway_config(self.gateway_config.as_ref())? {
+            Some(gateway_config) => {
+                headers::insert(
+                    &mut headers,
+                    headers::CONFIG,
+                    &gateway_config.header_value()?,
+                )?;
+                Some(gateway_config)
+            }
+            None => self.gateway_config.clone(),
+        };
+
+        Ok(Self {
+            http: self.http.clone(),
+            config: self.config.with_headers(&headers),
+            gateway_config,
+            retry_policy: self.retry_policy,
+            #[cfg(feature = "middleware")]
+            middleware: self.middleware.clone(),
+        })
     }
 }
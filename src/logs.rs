@@ -0,0 +1,881 @@
+//! Request logs recorded by Portkey.
+
+use std::{
+    collections::BTreeMap,
+    pin::Pin,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use url::form_urlencoded;
+
+use crate::{http::path, Client, Error, Metadata, PortkeyResponse, ResponseMeta, Result};
+
+/// Number of logs fetched per page by [`Logs::stream`].
+const PAGE_SIZE: u32 = 100;
+
+/// A stream of log entries fetched page by page.
+pub type LogStream = Pin<Box<dyn Stream<Item = Result<LogEntry>> + Send>>;
+
+/// Logs API.
+///
+/// Logs are either fetched directly, page by page, or exported in bulk: an
+/// export is created in draft state, started, and downloaded once it
+/// succeeded. Calls that bypass the gateway can be logged with
+/// [`Logs::insert`].
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::time::{Duration, SystemTime};
+///
+/// use portkey::{Client, LogExportRequest, LogFilter};
+///
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::new("your-portkey-api-key", "your-portkey-virtual-key");
+///
+/// let filter = LogFilter::new()
+///     .created_after(SystemTime::now() - Duration::from_secs(24 * 60 * 60))
+///     .model("gpt-4o")
+///     .status([500, 502, 503]);
+///
+/// let logs = client.logs();
+/// let export = logs.create_export(&LogExportRequest::new(filter)).await?;
+/// logs.start_export(&export.id).await?;
+/// logs.wait_for_export(&export.id, Duration::from_secs(5)).await?;
+/// let jsonl = logs.download_export(&export.id).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Logs<'c> {
+    client: &'c Client,
+}
+
+impl<'c> Logs<'c> {
+    pub(crate) fn new(client: &'c Client) -> Self {
+        Self { client }
+    }
+
+    /// Logs a request made without the gateway, e.g. to an on-device model,
+    /// so it shows up alongside gateway traffic. Returns Portkey's response
+    /// metadata.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidMetadata`] if the metadata is invalid,
+    /// [`Error::Api`] if the gateway returns an error response and
+    /// [`Error::Request`] if the request fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use portkey::{Client, CustomLog, LoggedRequest, LoggedResponse, Metadata};
+    /// use serde_json::json;
+    ///
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("your-portkey-api-key", "your-portkey-virtual-key");
+    ///
+    /// let request = LoggedRequest::new(
+    ///     "http://localhost:11434/v1/chat/completions",
+    ///     json!({ "model": "llama3", "messages": [{ "role": "user", "content": "Hi" }] }),
+    /// );
+    /// let response = LoggedResponse::new(json!({ "choices": [] }))
+    ///     .latency(Duration::from_millis(840));
+    ///
+    /// let log = CustomLog::new(request, response).metadata(Metadata::new().user("user-123"));
+    /// client.logs().insert(&log).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn insert(&self, log: &CustomLog) -> Result<ResponseMeta> {
+        log.validate()?;
+        self.client
+            .send_discarding(Method::POST, "/logs", Some(log))
+            .await
+    }
+
+    /// Logs several requests made without the gateway in one call, returning
+    /// Portkey's response metadata.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Logs::insert`].
+    pub async fn insert_many(&self, logs: &[CustomLog]) -> Result<ResponseMeta> {
+        for log in logs {
+            log.validate()?;
+        }
+        self.client
+            .send_discarding(Method::POST, "/logs", Some(logs))
+            .await
+    }
+
+    /// Retrieves the log with ID `log_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if an ID is empty, `.` or `..`,
+    /// [`Error::Api`] if the gateway returns an error response and
+    /// [`Error::Request`] if the request fails.
+    pub async fn retrieve(&self, log_id: &str) -> Result<PortkeyResponse<LogEntry>> {
+        self.client.get(&path(&["logs", log_id])?).await
+    }
+
+    /// Lists page `page`, starting at 0, of the logs matching `filter`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Logs::retrieve`].
+    pub async fn list(
+        &self,
+        filter: &LogFilter,
+        page: u32,
+        page_size: u32,
+    ) -> Result<PortkeyResponse<LogPage>> {
+        let query = {
+            let mut query = form_urlencoded::Serializer::new(String::new());
+            query
+                .append_pair("current_page", &page.to_string())
+                .append_pair("page_size", &page_size.to_string());
+            filter.append_query(&mut query)?;
+            query.finish()
+        };
+        self.client.get(&format!("/logs?{query}")).await
+    }
+
+    /// Returns all logs matching `filter`, fetching pages as the stream is
+    /// consumed.
+    ///
+    /// The stream ends at the first empty page, or once the number of logs
+    /// reported by the server has been fetched.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use futures::TryStreamExt;
+    /// use portkey::{Client, LogFilter, Metadata};
+    ///
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("your-portkey-api-key", "your-portkey-virtual-key");
+    ///
+    /// let filter = LogFilter::new().metadata(Metadata::new().user("user-123"));
+    /// let mut logs = client.logs().stream(filter);
+    /// while let Some(log) = logs.try_next().await? {
+    ///     println!("{:?} {:?}", log.id, log.cost);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stream(&self, filter: LogFilter) -> LogStream {
+        let client = self.client.clone();
+        let pages = stream::try_unfold(Some((0, 0)), move |state| {
+            let client = client.clone();
+            let filter = filter.clone();
+            async move {
+                let Some((page, fetched)) = state else {
+                    return Ok(None);
+                };
+                let response = client.logs().list(&filter, page, PAGE_SIZE).await?;
+                let LogPage { total, data } = response.into_body();
+                let next = next_page(page, fetched, data.len(), total);
+                Ok::<_, Error>(Some((stream::iter(data).map(Ok), next)))
+            }
+        });
+        Box::pin(pages.try_flatten())
+    }
+
+    /// Retrieves all logs with trace ID `trace_id`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Logs::retrieve`].
+    pub async fn retrieve_by_trace_id(&self, trace_id: &str) -> Result<Vec<LogEntry>> {
+        self.stream(LogFilter::new().trace_id(trace_id))
+            .try_collect()
+            .await
+    }
+
+    /// Creates a log export in draft state. The export runs once started with
+    /// [`Logs::start_export`].
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Logs::retrieve`].
+    pub async fn create_export(
+        &self,
+        request: &LogExportRequest,
+    ) -> Result<PortkeyResponse<LogExportCreated>> {
+        self.client.post("/logs/exports", request).await
+    }
+
+    /// Starts the log export `export_id`, returning Portkey's response
+    /// metadata.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Logs::retrieve`].
+    pub async fn start_export(&self, export_id: &str) -> Result<ResponseMeta> {
+        self.client
+            .send_discarding(
+                Method::POST,
+                &path(&["logs", "exports", export_id, "start"])?,
+                Some(&Map::new()),
+            )
+            .await
+    }
+
+    /// Retrieves the log export `export_id`, including its status.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Logs::retrieve`].
+    pub async fn retrieve_export(&self, export_id: &str) -> Result<PortkeyResponse<LogExport>> {
+        self.client
+            .get(&path(&["logs", "exports", export_id])?)
+            .await
+    }
+
+    /// Polls the log export `export_id` every `interval` while it is in
+    /// progress, and returns it once it succeeded, failed or was cancelled.
+    ///
+    /// Polling has no deadline; wrap the call in e.g. `tokio::time::timeout`
+    /// to bound how long to wait for a long-running export.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`] if `interval` is zero and
+    /// [`Error::ExportNotRunning`] if the export is still a draft, i.e. was
+    /// not started with [`Logs::start_export`], or has a status unknown to
+    /// this SDK. Otherwise same as [`Logs::retrieve`]. A failed export is
+    /// returned, not an error.
+    pub async fn wait_for_export(
+        &self,
+        export_id: &str,
+        interval: Duration,
+    ) -> Result<PortkeyResponse<LogExport>> {
+        if interval.is_zero() {
+            return Err(Error::InvalidArgument(
+                "the polling interval must not be zero".to_string(),
+            ));
+        }
+        loop {
+            let export = self.retrieve_export(export_id).await?;
+            match export.status {
+                LogExportStatus::InProgress => tokio::time::sleep(interval).await,
+                LogExportStatus::Draft | LogExportStatus::Other(_) => {
+                    return Err(Error::ExportNotRunning {
+                        export_id: export_id.to_string(),
+                        status: export.into_body().status,
+                    });
+                }
+                LogExportStatus::Success | LogExportStatus::Failed | LogExportStatus::Cancelled => {
+                    return Ok(export)
+                }
+            }
+        }
+    }
+
+    /// Returns a signed URL to download the result of the log export
+    /// `export_id`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Logs::retrieve`].
+    pub async fn export_download_url(
+        &self,
+        export_id: &str,
+    ) -> Result<PortkeyResponse<LogExportDownload>> {
+        self.client
+            .get(&path(&["logs", "exports", export_id, "download"])?)
+            .await
+    }
+
+    /// Downloads the result of the succeeded log export `export_id`, one JSON
+    /// log entry per line.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Logs::retrieve`], including for errors of the download.
+    pub async fn download_export(&self, export_id: &str) -> Result<Vec<u8>> {
+        let download = self.export_download_url(export_id).await?;
+        self.client.download(&download.signed_url).await
+    }
+}
+
+/// A request made without the gateway, together with its response.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CustomLog {
+    /// The request sent to the provider.
+    request: LoggedRequest,
+    /// The provider's response.
+    response: LoggedResponse,
+    /// Metadata for filtering the log.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metadata: Option<Metadata>,
+}
+
+impl CustomLog {
+    /// Creates a log of `request` and its `response`.
+    pub fn new(request: LoggedRequest, response: LoggedResponse) -> Self {
+        Self {
+            request,
+            response,
+            metadata: None,
+        }
+    }
+
+    /// Sets metadata for filtering the log.
+    pub fn metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// Checks that the metadata is within Portkey's limits.
+    fn validate(&self) -> Result<()> {
+        match &self.metadata {
+            Some(metadata) => metadata.validate(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The request of a [`CustomLog`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LoggedRequest {
+    /// URL the request was sent to.
+    url: String,
+    /// HTTP method of the request.
+    method: String,
+    /// Request headers.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    headers: BTreeMap<String, String>,
+    /// Request body, e.g. a chat completion request.
+    body: Value,
+}
+
+impl LoggedRequest {
+    /// Creates a `POST` request to `url` with a JSON `body`.
+    pub fn new(url: impl Into<String>, body: impl Into<Value>) -> Self {
+        Self {
+            url: url.into(),
+            method: "POST".to_string(),
+            headers: BTreeMap::new(),
+            body: body.into(),
+        }
+    }
+
+    /// Sets the HTTP method of the request.
+    pub fn method(mut self, method: impl Into<String>) -> Self {
+        self.method = method.into();
+        self
+    }
+
+    /// Adds a request header. Leave out credentials such as API keys.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(name.into(), value.into());
+        self
+    }
+}
+
+/// The response of a [`CustomLog`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LoggedResponse {
+    /// HTTP status of the response.
+    status: u16,
+    /// Response headers.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    headers: BTreeMap<String, String>,
+    /// Response body, e.g. a chat completion.
+    body: Value,
+    /// Time in milliseconds until the response was received.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_time: Option<u64>,
+}
+
+impl LoggedResponse {
+    /// Creates a `200 OK` response with a JSON `body`.
+    pub fn new(body: impl Into<Value>) -> Self {
+        Self {
+            status: 200,
+            headers: BTreeMap::new(),
+            body: body.into(),
+            response_time: None,
+        }
+    }
+
+    /// Sets the HTTP status of the response.
+    pub fn status(mut self, status: u16) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Adds a response header.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(name.into(), value.into());
+        self
+    }
+
+    /// Sets the time until the response was received, with millisecond
+    /// precision.
+    pub fn latency(mut self, latency: Duration) -> Self {
+        self.response_time = Some(u64::try_from(latency.as_millis()).unwrap_or(u64::MAX));
+        self
+    }
+}
+
+/// Filter selecting logs to list or export.
+///
+/// Conditions are combined: a log must match all of them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LogFilter {
+    /// Earliest creation time, in RFC 3339 format.
+    #[serde(
+        rename = "time_of_generation_min",
+        skip_serializing_if = "Option::is_none"
+    )]
+    created_after: Option<String>,
+    /// Latest creation time, in RFC 3339 format.
+    #[serde(
+        rename = "time_of_generation_max",
+        skip_serializing_if = "Option::is_none"
+    )]
+    created_before: Option<String>,
+    /// Accepted response status codes.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    status: Vec<u16>,
+    /// Accepted models.
+    #[serde(rename = "ai_model", skip_serializing_if = "Vec::is_empty")]
+    models: Vec<String>,
+    /// Metadata the logs must carry.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metadata: Option<Metadata>,
+    /// Trace ID of the logs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    trace_id: Option<String>,
+}
+
+impl LogFilter {
+    /// Creates a filter matching all logs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Matches logs created at or after `time`.
+    pub fn created_after(mut self, time: SystemTime) -> Self {
+        self.created_after = Some(rfc3339(time));
+        self
+    }
+
+    /// Matches logs created at or before `time`.
+    pub fn created_before(mut self, time: SystemTime) -> Self {
+        self.created_before = Some(rfc3339(time));
+        self
+    }
+
+    /// Matches logs whose response has one of the status codes.
+    pub fn status(mut self, status: impl IntoIterator<Item = u16>) -> Self {
+        self.status.extend(status);
+        self
+    }
+
+    /// Matches logs of `model`. Repeated calls match any of the models.
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.models.push(model.into());
+        self
+    }
+
+    /// Matches logs carrying all keys of `metadata` with the same values.
+    pub fn metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// Matches logs with trace ID `trace_id`.
+    pub fn trace_id(mut self, trace_id: impl Into<String>) -> Self {
+        self.trace_id = Some(trace_id.into());
+        self
+    }
+
+    /// Appends the filter to a query string. Lists are comma-separated and
+    /// metadata is JSON-encoded.
+    fn append_query(&self, query: &mut form_urlencoded::Serializer<'_, String>) -> Result<()> {
+        let Value::Object(fields) = serde_json::to_value(self)? else {
+            unreachable!("a filter serializes to an object");
+        };
+        for (name, value) in fields {
+            let value = match value {
+                Value::String(value) => value,
+                Value::Array(values) => values
+                    .iter()
+                    .map(|value| match value {
+                        Value::String(value) => value.clone(),
+                        value => value.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(","),
+                value => value.to_string(),
+            };
+            query.append_pair(&name, &value);
+        }
+        Ok(())
+    }
+}
+
+/// Returns the page to fetch after page `page` with `page_len` logs, and the
+/// number of logs fetched so far, or `None` if all logs were fetched.
+///
+/// `fetched` counts the logs of the previous pages and `total` is the number
+/// of matching logs reported by the server, if any. The server may return
+/// fewer logs per page than requested, so a short page does not end the
+/// stream.
+fn next_page(page: u32, fetched: u64, page_len: usize, total: Option<u64>) -> Option<(u32, u64)> {
+    let fetched = fetched + page_len as u64;
+    if page_len == 0 || total.is_some_and(|total| fetched >= total) {
+        return None;
+    }
+    Some((page + 1, fetched))
+}
+
+/// Formats `time` in RFC 3339 format with second precision, e.g.
+/// `2024-10-20T08:30:00Z`.
+fn rfc3339(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+    let (days, secs) = (secs / 86_400, secs % 86_400);
+
+    // Civil date from days since the epoch, see
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        secs / 3_600,
+        secs % 3_600 / 60,
+        secs % 60
+    )
+}
+
+/// A page of logs.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct LogPage {
+    /// Number of logs matching the filter across all pages.
+    #[serde(default)]
+    pub total: Option<u64>,
+    /// Logs of the page.
+    pub data: Vec<LogEntry>,
+}
+
+/// A request logged by Portkey.
+///
+/// Fields not covered by this SDK are kept in [`LogEntry::extra`].
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct LogEntry {
+    /// ID of the log.
+    #[serde(default)]
+    pub id: Option<String>,
+    /// Trace ID of the request.
+    #[serde(default)]
+    pub trace_id: Option<String>,
+    /// Creation time of the log.
+    #[serde(default)]
+    pub created_at: Option<String>,
+    /// Provider that served the request.
+    #[serde(default, alias = "ai_org")]
+    pub ai_provider: Option<String>,
+    /// Model that served the request.
+    #[serde(default)]
+    pub ai_model: Option<String>,
+    /// Response status code.
+    #[serde(default)]
+    pub response_status_code: Option<u16>,
+    /// Time to respond in milliseconds.
+    #[serde(default)]
+    pub response_time: Option<u64>,
+    /// Total number of tokens.
+    #[serde(default)]
+    pub total_units: Option<u64>,
+    /// Cost of the request.
+    #[serde(default)]
+    pub cost: Option<f64>,
+    /// Metadata of the request.
+    #[serde(default)]
+    pub metadata: Option<Value>,
+    /// Request body.
+    #[serde(default)]
+    pub request: Option<Value>,
+    /// Response body.
+    #[serde(default)]
+    pub response: Option<Value>,
+    /// All other fields of the log.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Request to export the logs matching a filter.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LogExportRequest {
+    /// Logs to export.
+    filters: LogFilter,
+    /// Log fields to include, all if empty.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    requested_data: Vec<String>,
+    /// Description shown in Portkey.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    /// Workspace to export from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    workspace_id: Option<String>,
+}
+
+impl LogExportRequest {
+    /// Creates a request exporting the logs matching `filter`.
+    pub fn new(filter: LogFilter) -> Self {
+        Self {
+            filters: filter,
+            ..Self::default()
+        }
+    }
+
+    /// Limits the export to the given log fields, e.g. `id`, `trace_id` and
+    /// `cost`.
+    pub fn requested_data(mut self, fields: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.requested_data = fields.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the description shown in Portkey.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Exports the logs of workspace `workspace_id` instead of the API key's
+    /// default workspace.
+    pub fn workspace_id(mut self, workspace_id: impl Into<String>) -> Self {
+        self.workspace_id = Some(workspace_id.into());
+        self
+    }
+}
+
+/// A newly created log export.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct LogExportCreated {
+    /// ID of the export.
+    pub id: String,
+    /// Number of logs the export will contain.
+    #[serde(default)]
+    pub total: Option<u64>,
+}
+
+/// A log export.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct LogExport {
+    /// ID of the export.
+    pub id: String,
+    /// Status of the export.
+    pub status: LogExportStatus,
+    /// Description shown in Portkey.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Creation time of the export.
+    #[serde(default)]
+    pub created_at: Option<String>,
+    /// Time of the last status change.
+    #[serde(default)]
+    pub last_updated_at: Option<String>,
+    /// All other fields of the export.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Status of a [`LogExport`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(from = "String")]
+#[non_exhaustive]
+pub enum LogExportStatus {
+    /// Created but not started.
+    Draft,
+    /// Running.
+    InProgress,
+    /// Finished, the result can be downloaded.
+    Success,
+    /// Finished without a result.
+    Failed,
+    /// Cancelled before it finished.
+    Cancelled,
+    /// A status not known to this SDK.
+    Other(String),
+}
+
+impl LogExportStatus {
+    /// Returns `true` if the export will not change status anymore.
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Success | Self::Failed | Self::Cancelled)
+    }
+}
+
+impl From<String> for LogExportStatus {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "draft" => Self::Draft,
+            "in_progress" => Self::InProgress,
+            "success" => Self::Success,
+            "failed" => Self::Failed,
+            "cancelled" => Self::Cancelled,
+            _ => Self::Other(value),
+        }
+    }
+}
+
+/// Download location of a log export.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct LogExportDownload {
+    /// Signed URL of the export result.
+    pub signed_url: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn formats_rfc3339() {
+        assert_eq!(rfc3339(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+        assert_eq!(rfc3339(at(951_782_400)), "2000-02-29T00:00:00Z");
+        assert_eq!(rfc3339(at(1_709_164_800)), "2024-02-29T00:00:00Z");
+        assert_eq!(rfc3339(at(1_729_413_000)), "2024-10-20T08:30:00Z");
+        assert_eq!(rfc3339(at(1_735_689_599)), "2024-12-31T23:59:59Z");
+        assert_eq!(
+            rfc3339(UNIX_EPOCH - Duration::from_secs(1)),
+            "1970-01-01T00:00:00Z"
+        );
+    }
+
+    #[test]
+    fn appends_filter_to_query() {
+        let filter = LogFilter::new()
+            .created_after(at(1_729_413_000))
+            .created_before(at(1_729_416_600))
+            .status([200, 500])
+            .model("gpt-4o")
+            .model("gpt-4o-mini")
+            .metadata(Metadata::new().user("u 1").insert("team", "a&b"))
+            .trace_id("trace-1");
+
+        let mut query = form_urlencoded::Serializer::new(String::new());
+        filter.append_query(&mut query).unwrap();
+        let query = query.finish();
+
+        let mut pairs: Vec<_> = form_urlencoded::parse(query.as_bytes())
+            .into_owned()
+            .collect();
+        pairs.sort();
+        let expected = [
+            ("ai_model", "gpt-4o,gpt-4o-mini"),
+            ("metadata", r#"{"_user":"u 1","team":"a&b"}"#),
+            ("status", "200,500"),
+            ("time_of_generation_max", "2024-10-20T09:30:00Z"),
+            ("time_of_generation_min", "2024-10-20T08:30:00Z"),
+            ("trace_id", "trace-1"),
+        ]
+        .map(|(name, value)| (name.to_string(), value.to_string()));
+        assert_eq!(pairs, expected);
+        assert!(query.contains("metadata=%7B%22_user%22%3A%22u+1%22%2C%22team%22%3A%22a%26b%22%7D"));
+    }
+
+    #[test]
+    fn pages_until_empty_page_or_total() {
+        // A server capping the page size below `PAGE_SIZE` keeps paging.
+        assert_eq!(next_page(0, 0, 50, Some(120)), Some((1, 50)));
+        assert_eq!(next_page(1, 50, 50, Some(120)), Some((2, 100)));
+        assert_eq!(next_page(2, 100, 20, Some(120)), None);
+        assert_eq!(next_page(0, 0, 50, None), Some((1, 50)));
+        assert_eq!(next_page(1, 50, 0, None), None);
+        assert_eq!(next_page(0, 0, 0, Some(0)), None);
+    }
+
+    #[tokio::test]
+    async fn rejects_zero_polling_interval() {
+        let client = Client::new("portkey-key", "virtual-key");
+
+        assert!(matches!(
+            client
+                .logs()
+                .wait_for_export("export-1", Duration::ZERO)
+                .await,
+            Err(Error::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn serializes_custom_log() {
+        let request = LoggedRequest::new(
+            "http://localhost:11434/v1/chat/completions",
+            json!({ "model": "llama3" }),
+        )
+        .header("content-type", "application/json");
+        let response = LoggedResponse::new(json!({ "choices": [] }))
+            .status(201)
+            .header("x-request-id", "req-1")
+            .latency(Duration::from_millis(840));
+        let log = CustomLog::new(request, response).metadata(Metadata::new().user("user-123"));
+
+        assert_eq!(
+            serde_json::to_value(&log).unwrap(),
+            json!({
+                "request": {
+                    "url": "http://localhost:11434/v1/chat/completions",
+                    "method": "POST",
+                    "headers": { "content-type": "application/json" },
+                    "body": { "model": "llama3" }
+                },
+                "response": {
+                    "status": 201,
+                    "headers": { "x-request-id": "req-1" },
+                    "body": { "choices": [] },
+                    "response_time": 840
+                },
+                "metadata": { "_user": "user-123" }
+            })
+        );
+
+        let minimal = CustomLog::new(
+            LoggedRequest::new("http://localhost", json!({})).method("GET"),
+            LoggedResponse::new(json!({})),
+        );
+        assert_eq!(
+            serde_json::to_value(&minimal).unwrap(),
+            json!({
+                "request": { "url": "http://localhost", "method": "GET", "body": {} },
+                "response": { "status": 200, "body": {} }
+            })
+        );
+    }
+
+    #[test]
+    fn appends_nothing_for_empty_filter() {
+        let mut query = form_urlencoded::Serializer::new(String::new());
+        LogFilter::new().append_query(&mut query).unwrap();
+        assert_eq!(query.finish(), "");
+    }
+}
@@ -0,0 +1,99 @@
+//! Request metadata sent via `x-portkey-metadata`.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::{Error, Result};
+
+/// Maximum length of a metadata key or value accepted by Portkey.
+const MAX_LENGTH: usize = 128;
+
+/// Metadata attached to requests for filtering and analytics in Portkey.
+///
+/// Portkey's reserved keys have dedicated setters; custom keys are added with
+/// [`Metadata::insert`]. Metadata is attached to all requests of a client with
+/// [`ClientBuilder::metadata`](crate::ClientBuilder::metadata) or to a single
+/// request with [`RequestOptions::metadata`](crate::RequestOptions::metadata).
+///
+/// # Examples
+///
+/// ```rust
+/// use portkey::Metadata;
+///
+/// let metadata = Metadata::new()
+///     .user("user-123")
+///     .environment("production")
+///     .insert("plan", "premium");
+///
+/// assert_eq!(metadata.get("_user"), Some("user-123"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(transparent)]
+pub struct Metadata(BTreeMap<String, String>);
+
+impl Metadata {
+    /// Creates empty metadata.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the `_user` key identifying the end user of the request.
+    pub fn user(self, user: impl Into<String>) -> Self {
+        self.insert("_user", user)
+    }
+
+    /// Sets the `_environment` key, e.g. `production` or `staging`.
+    pub fn environment(self, environment: impl Into<String>) -> Self {
+        self.insert("_environment", environment)
+    }
+
+    /// Sets the `_prompt` key identifying the prompt used.
+    pub fn prompt(self, prompt: impl Into<String>) -> Self {
+        self.insert("_prompt", prompt)
+    }
+
+    /// Sets a custom key.
+    pub fn insert(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.0.insert(key.into(), value.into());
+        self
+    }
+
+    /// Returns the value of `key`, if set.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(String::as_str)
+    }
+
+    /// Returns `true` if no keys are set.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Checks that all keys and values are within Portkey's length limits.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidMetadata`] naming the first key that exceeds
+    /// the limits.
+    pub fn validate(&self) -> Result<()> {
+        for (key, value) in &self.0 {
+            if key.is_empty() || key.chars().count() > MAX_LENGTH {
+                return Err(Error::InvalidMetadata(format!(
+                    "key `{key}` must be between 1 and {MAX_LENGTH} characters"
+                )));
+            }
+            if value.chars().count() > MAX_LENGTH {
+                return Err(Error::InvalidMetadata(format!(
+                    "value of `{key}` must be at most {MAX_LENGTH} characters"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the value of the `x-portkey-metadata` header.
+    pub(crate) fn header_value(&self) -> Result<String> {
+        self.validate()?;
+        Ok(serde_json::to_string(self)?)
+    }
+}
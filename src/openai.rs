@@ -0,0 +1,145 @@
+//! `async-openai` configuration backed by Portkey headers.
+
+use async_openai::config::{Config, OPENAI_BETA_HEADER};
+use reqwest::header::{HeaderMap, HeaderValue};
+use secrecy::Secret;
+
+use crate::{headers, ProviderOptions};
+
+/// The [`async_openai`] configuration used by a Portkey [`Client`](crate::Client).
+///
+/// It points `async-openai` at the Portkey gateway and attaches the Portkey
+/// headers to every request. Instances are created by
+/// [`ClientBuilder::build`](crate::ClientBuilder::build) and
+/// [`Client::with_options`](crate::Client::with_options).
+#[derive(Debug, Clone)]
+pub struct PortkeyConfig {
+    /// Base URL of the Portkey gateway.
+    api_base: String,
+    /// Key sent in the `Authorization` header.
+    api_key: Secret<String>,
+    /// Headers sent with every request, including `Authorization`.
+    headers: HeaderMap,
+    /// Whether requests without a trace ID get a generated one.
+    auto_trace_id: bool,
+    /// Provider options whose headers are refreshed for every request.
+    dynamic_provider: Option<ProviderOptions>,
+}
+
+impl PortkeyConfig {
+    /// Creates a config from a base URL, API key and the headers to send.
+    pub(crate) fn new(api_base: String, api_key: String, headers: HeaderMap) -> Self {
+        Self {
+            api_base,
+            api_key: Secret::new(api_key),
+            headers,
+            auto_trace_id: false,
+            dynamic_provider: None,
+        }
+    }
+
+    /// Generates a trace ID for every request that does not carry one.
+    pub(crate) fn with_auto_trace_id(mut self, auto_trace_id: bool) -> Self {
+        self.auto_trace_id = auto_trace_id;
+        self
+    }
+
+    /// Refreshes the headers of `provider_options` for every request if they
+    /// change over time.
+    pub(crate) fn with_provider_options(
+        mut self,
+        provider_options: Option<ProviderOptions>,
+    ) -> Self {
+        self.dynamic_provider = provider_options.filter(ProviderOptions::is_dynamic);
+        self
+    }
+
+    /// Returns a copy of this config with `headers` merged over the existing
+    /// headers.
+    pub(crate) fn with_headers(&self, headers: &HeaderMap) -> Self {
+        let mut config = self.clone();
+        for name in headers.keys() {
+            config.headers.remove(name);
+        }
+        for (name, value) in headers {
+            config.headers.append(name, value.clone());
+        }
+        config
+    }
+
+    /// Returns `true` if the header `name` is sent with every request.
+    pub(crate) fn has_header(&self, name: &str) -> bool {
+        self.headers.contains_key(name)
+    }
+
+    /// Returns the headers of a request sent by the SDK itself.
+    ///
+    /// Unlike [`Config::headers`], these do not include the `OpenAI-Beta`
+    /// header, which only `async-openai`'s Assistants API calls need.
+    pub(crate) fn request_headers(&self) -> HeaderMap {
+        let mut headers = self.headers.clone();
+        if let Some(provider) = &self.dynamic_provider {
+            // The headers were validated when the client was built. Should
+            // refreshed credentials be invalid, the previous ones are kept.
+            let mut refreshed = headers.clone();
+            if provider.apply(&mut refreshed).is_ok() {
+                headers = refreshed;
+            }
+        }
+        // A new ID per call: the SDK builds the headers once per call, but
+        // `async-openai` rebuilds them for every attempt.
+        if self.auto_trace_id && !headers.contains_key(headers::TRACE_ID) {
+            let trace_id = HeaderValue::from_str(&headers::generate_trace_id())
+                .expect("UUID is a valid header value");
+            headers.insert(headers::TRACE_ID, trace_id);
+        }
+        headers
+    }
+}
+
+impl Config for PortkeyConfig {
+    fn headers(&self) -> HeaderMap {
+        let mut headers = self.request_headers();
+        // Calls to the Assistants API require the beta header.
+        headers.insert(
+            OPENAI_BETA_HEADER,
+            HeaderValue::from_static("assistants=v2"),
+        );
+        headers
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.api_base, path)
+    }
+
+    fn query(&self) -> Vec<(&str, &str)> {
+        vec![]
+    }
+
+    fn api_base(&self) -> &str {
+        &self.api_base
+    }
+
+    fn api_key(&self) -> &Secret<String> {
+        &self.api_key
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn beta_header_is_only_sent_by_async_openai() {
+        let config = PortkeyConfig::new(
+            "https://api.portkey.ai/v1".to_string(),
+            "api-key".to_string(),
+            HeaderMap::new(),
+        )
+        .with_auto_trace_id(true);
+
+        assert!(!config.request_headers().contains_key(OPENAI_BETA_HEADER));
+        assert!(config.request_headers().contains_key(headers::TRACE_ID));
+        assert_eq!(config.headers()[OPENAI_BETA_HEADER], "assistants=v2");
+    }
+}
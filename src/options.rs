@@ -0,0 +1,218 @@
+//! Per-request overrides of the client's Portkey headers.
+
+use reqwest::header::HeaderMap;
+
+use crate::{
+    config::{CacheConfig, Config, ConfigSource},
+    headers, Metadata, Result,
+};
+
+/// Portkey headers applied to individual requests on top of the client
+/// defaults.
+///
+/// Options are applied with [`Client::with_options`](crate::Client::with_options),
+/// which returns a client sharing the original connection pool. Headers set here
+/// replace the client's values for the same header.
+///
+/// # Examples
+///
+/// ```rust
+/// use portkey::{Client, RequestOptions};
+///
+/// let client = Client::new("your-portkey-api-key", "your-portkey-virtual-key");
+///
+/// let options = RequestOptions::new()
+///     .virtual_key("another-virtual-key")
+///     .cache_force_refresh(true);
+///
+/// let openai = client.with_options(&options).unwrap().openai();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RequestOptions {
+    /// Portkey virtual key of the provider.
+    virtual_key: Option<String>,
+    /// Trace ID correlating the request with Portkey's logs.
+    trace_id: Option<String>,
+    /// Metadata sent as `x-portkey-metadata`.
+    metadata: Option<Metadata>,
+    /// Gateway config replacing the client's config.
+    config: Option<ConfigSource>,
+    /// Response caching added to the gateway config.
+    cache: Option<CacheConfig>,
+    /// Whether the gateway refreshes a cached response.
+    cache_force_refresh: Option<bool>,
+    /// Additional raw headers.
+    headers: Vec<(String, String)>,
+}
+
+impl RequestOptions {
+    /// Creates empty request options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Overrides the Portkey virtual key.
+    ///
+    /// Clients authenticating with [`Auth::Provider`](crate::Auth::Provider)
+    /// or [`ProviderOptions`](crate::ProviderOptions) send the provider's own
+    /// credentials, so they cannot switch to a virtual key.
+    pub fn virtual_key(mut self, virtual_key: impl Into<String>) -> Self {
+        self.virtual_key = Some(virtual_key.into());
+        self
+    }
+
+    /// Sets the trace ID sent as `x-portkey-trace-id`.
+    pub fn trace_id(mut self, trace_id: impl Into<String>) -> Self {
+        self.trace_id = Some(trace_id.into());
+        self
+    }
+
+    /// Sets a newly generated UUID as trace ID.
+    ///
+    /// The generated ID can be read back with [`RequestOptions::get_trace_id`]
+    /// to correlate the response with Portkey's logs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use portkey::RequestOptions;
+    ///
+    /// let options = RequestOptions::new().generate_trace_id();
+    /// let trace_id = options.get_trace_id().unwrap();
+    /// assert_eq!(trace_id.len(), 36);
+    /// ```
+    pub fn generate_trace_id(self) -> Self {
+        self.trace_id(headers::generate_trace_id())
+    }
+
+    /// Returns the trace ID sent with the request, if any.
+    pub fn get_trace_id(&self) -> Option<&str> {
+        self.trace_id.as_deref()
+    }
+
+    /// Sets the metadata sent as `x-portkey-metadata`.
+    ///
+    /// Replaces the client's metadata for this request.
+    pub fn metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// Overrides the gateway config with an inline config.
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = Some(ConfigSource::Inline(Box::new(config)));
+        self
+    }
+
+    /// Overrides the gateway config with the ID of a saved config.
+    pub fn config_id(mut self, config_id: impl Into<String>) -> Self {
+        self.config = Some(ConfigSource::Saved(config_id.into()));
+        self
+    }
+
+    /// Sets response caching for the request.
+    ///
+    /// The cache settings are added to the request's gateway config, or to the
+    /// client's inline config if the request sets none. They cannot be added to
+    /// a saved config.
+    pub fn cache(mut self, cache: CacheConfig) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Forces the gateway to bypass and refresh its cache for the request.
+    pub fn cache_force_refresh(mut self, force_refresh: bool) -> Self {
+        self.cache_force_refresh = Some(force_refresh);
+        self
+    }
+
+    /// Sets an arbitrary header, e.g. a Portkey header without a dedicated
+    /// setter.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Returns the gateway config of the request, if it differs from
+    /// `client_config`.
+    pub(crate) fn gateway_config(
+        &self,
+        client_config: Option<&ConfigSource>,
+    ) -> Result<Option<ConfigSource>> {
+        match &self.cache {
+            Some(cache) => {
+                let config = self.config.clone().or_else(|| client_config.cloned());
+                ConfigSource::update(config, "cache settings", |config| {
+                    config.cache(cache.clone())
+                })
+                .map(Some)
+            }
+            None => Ok(self.config.clone()),
+        }
+    }
+
+    /// Converts the options into the headers they override, except for the
+    /// gateway config.
+    pub(crate) fn header_map(&self) -> Result<HeaderMap> {
+        let mut map = HeaderMap::new();
+        if let Some(virtual_key) = &self.virtual_key {
+            headers::insert(&mut map, headers::VIRTUAL_KEY, virtual_key)?;
+        }
+        if let Some(trace_id) = &self.trace_id {
+            headers::insert(&mut map, headers::TRACE_ID, trace_id)?;
+        }
+        if let Some(metadata) = &self.metadata {
+            headers::insert(&mut map, headers::METADATA, &metadata.header_value()?)?;
+        }
+        if let Some(force_refresh) = self.cache_force_refresh {
+            headers::insert(
+                &mut map,
+                headers::CACHE_FORCE_REFRESH,
+                if force_refresh { "true" } else { "false" },
+            )?;
+        }
+        for (name, value) in &self.headers {
+            headers::insert(&mut map, name, value)?;
+        }
+        Ok(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Auth, AzureOptions, Client, Error, ProviderOptions};
+
+    #[test]
+    fn virtual_key_cannot_override_provider_authentication() {
+        let options = RequestOptions::new().virtual_key("another-virtual-key");
+        let provider = Client::builder()
+            .api_key("portkey-key")
+            .auth(Auth::Provider {
+                provider: "openai".into(),
+                api_key: "sk-openai".into(),
+            })
+            .build()
+            .unwrap();
+        let azure = Client::builder()
+            .api_key("portkey-key")
+            .provider_options(ProviderOptions::Azure(AzureOptions::new(
+                "resource",
+                "deployment",
+                "2024-06-01",
+                "azure-key",
+            )))
+            .build()
+            .unwrap();
+
+        for client in [provider, azure] {
+            assert!(matches!(
+                client.with_options(&options),
+                Err(Error::InvalidArgument(_))
+            ));
+        }
+        assert!(Client::new("portkey-key", "virtual-key")
+            .with_options(&options)
+            .is_ok());
+    }
+}
@@ -0,0 +1,282 @@
+//! Saved prompt templates from Portkey's prompt library.
+
+use std::fmt;
+
+use async_openai::types::{
+    ChatCompletionRequestMessage, CreateChatCompletionRequest, CreateChatCompletionResponse,
+    CreateChatCompletionStreamResponse,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{http, Client, EventStream, PortkeyResponse, Result};
+
+/// Prompts API.
+///
+/// Prompts are edited in Portkey's prompt library and referenced by their ID.
+/// Variables are any [`Serialize`] type that serializes to a JSON object, such
+/// as a struct or a map.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use portkey::{Client, PromptRef};
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Variables<'a> {
+///     customer: &'a str,
+///     question: &'a str,
+/// }
+///
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::new("your-portkey-api-key", "your-portkey-virtual-key");
+///
+/// let variables = Variables {
+///     customer: "Ada",
+///     question: "Where is my order?",
+/// };
+/// let response = client
+///     .prompts()
+///     .completions(PromptRef::new("pp-support-xxx").version(3), &variables)
+///     .await?;
+/// println!("{:?}", response.choices);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Prompts<'c> {
+    client: &'c Client,
+}
+
+impl<'c> Prompts<'c> {
+    pub(crate) fn new(client: &'c Client) -> Self {
+        Self { client }
+    }
+
+    /// Runs a chat completion with the prompt rendered from `variables`.
+    ///
+    /// The response carries Portkey's response metadata, see
+    /// [`PortkeyResponse::meta`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidArgument`](crate::Error::InvalidArgument) if
+    /// the prompt ID is empty, `.` or `..`, [`Error::Api`](crate::Error::Api)
+    /// if the gateway returns an error response, e.g. for an unknown prompt or
+    /// a missing variable, and [`Error::Request`](crate::Error::Request) if the
+    /// request fails.
+    pub async fn completions<V>(
+        &self,
+        prompt: impl Into<PromptRef>,
+        variables: &V,
+    ) -> Result<PortkeyResponse<CreateChatCompletionResponse>>
+    where
+        V: Serialize + ?Sized,
+    {
+        let path = prompt.into().path("completions")?;
+        let body = PromptRequest {
+            variables,
+            stream: false,
+        };
+        self.client.post(&path, &body).await
+    }
+
+    /// Runs a chat completion with the prompt rendered from `variables`,
+    /// streaming the response chunks as they are generated.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Prompts::completions`]. Errors while reading the stream are
+    /// returned as its items.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use portkey::Client;
+    /// use serde_json::json;
+    ///
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = Client::new("your-portkey-api-key", "your-portkey-virtual-key");
+    ///
+    /// let mut stream = client
+    ///     .prompts()
+    ///     .completions_stream("pp-support-xxx", &json!({ "customer": "Ada" }))
+    ///     .await?;
+    /// while let Some(chunk) = stream.next().await {
+    ///     for choice in chunk?.choices {
+    ///         print!("{}", choice.delta.content.unwrap_or_default());
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn completions_stream<V>(
+        &self,
+        prompt: impl Into<PromptRef>,
+        variables: &V,
+    ) -> Result<EventStream<CreateChatCompletionStreamResponse>>
+    where
+        V: Serialize + ?Sized,
+    {
+        let path = prompt.into().path("completions")?;
+        let body = PromptRequest {
+            variables,
+            stream: true,
+        };
+        self.client.post_stream(&path, &body).await
+    }
+
+    /// Renders the prompt with `variables` without running a completion.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Prompts::completions`].
+    pub async fn render<V>(
+        &self,
+        prompt: impl Into<PromptRef>,
+        variables: &V,
+    ) -> Result<PortkeyResponse<RenderedPrompt>>
+    where
+        V: Serialize + ?Sized,
+    {
+        let path = prompt.into().path("render")?;
+        let body = PromptRequest {
+            variables,
+            stream: false,
+        };
+        let response: PortkeyResponse<Rendered> = self.client.post(&path, &body).await?;
+        let (rendered, meta) = response.into_parts();
+        Ok(PortkeyResponse::new(rendered.data, meta))
+    }
+}
+
+/// Body of prompt requests.
+#[derive(Serialize)]
+struct PromptRequest<'v, V: ?Sized> {
+    variables: &'v V,
+    stream: bool,
+}
+
+/// Response envelope of the render endpoint.
+#[derive(Deserialize)]
+struct Rendered {
+    data: RenderedPrompt,
+}
+
+/// A prompt template together with the version to use.
+///
+/// Converting an ID with `From` selects the version currently published in
+/// the prompt library.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptRef {
+    /// ID of the prompt.
+    id: String,
+    /// Version of the prompt, the published one if unset.
+    version: Option<PromptVersion>,
+}
+
+impl PromptRef {
+    /// References the published version of the prompt `id`.
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            version: None,
+        }
+    }
+
+    /// Selects a version of the prompt, e.g. a version number,
+    /// [`PromptVersion::Latest`] or a label.
+    pub fn version(mut self, version: impl Into<PromptVersion>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Returns the path of the prompt endpoint `endpoint`.
+    fn path(&self, endpoint: &str) -> Result<String> {
+        let prompt = match &self.version {
+            Some(version) => format!("{}@{version}", self.id),
+            None => self.id.clone(),
+        };
+        http::path(&["prompts", &prompt, endpoint])
+    }
+}
+
+impl From<&str> for PromptRef {
+    fn from(id: &str) -> Self {
+        Self::new(id)
+    }
+}
+
+impl From<String> for PromptRef {
+    fn from(id: String) -> Self {
+        Self::new(id)
+    }
+}
+
+/// Version of a prompt template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PromptVersion {
+    /// A version number.
+    Number(u32),
+    /// The most recent version, even if it is not published.
+    Latest,
+    /// A version label, e.g. `production`.
+    Label(String),
+}
+
+impl fmt::Display for PromptVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(number) => write!(f, "{number}"),
+            Self::Latest => f.write_str("latest"),
+            Self::Label(label) => f.write_str(label),
+        }
+    }
+}
+
+impl From<u32> for PromptVersion {
+    fn from(number: u32) -> Self {
+        Self::Number(number)
+    }
+}
+
+impl From<&str> for PromptVersion {
+    fn from(label: &str) -> Self {
+        Self::Label(label.to_string())
+    }
+}
+
+impl From<String> for PromptVersion {
+    fn from(label: String) -> Self {
+        Self::Label(label)
+    }
+}
+
+/// A prompt template rendered with its variables.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RenderedPrompt {
+    /// Model set in the prompt template.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Messages with the variables filled in.
+    pub messages: Vec<ChatCompletionRequestMessage>,
+    /// Other parameters of the prompt template, e.g. `temperature`.
+    #[serde(flatten)]
+    pub parameters: Map<String, Value>,
+}
+
+impl RenderedPrompt {
+    /// Converts the rendered prompt into a chat completion request, e.g. to
+    /// send it with [`Client::chat`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Json`](crate::Error::Json) if the template has no
+    /// model or a parameter is not a valid chat completion parameter.
+    pub fn into_request(self) -> Result<CreateChatCompletionRequest> {
+        Ok(serde_json::from_value(serde_json::to_value(self)?)?)
+    }
+}
@@ -0,0 +1,514 @@
+//! Provider-specific options for routing requests through the Portkey gateway.
+
+use std::{fmt, fs, path::Path};
+
+use reqwest::header::{HeaderMap, AUTHORIZATION};
+use serde_json::Value;
+
+use crate::{
+    credentials::{AccessToken, AwsCredentials, CredentialProvider},
+    headers, Error, Result,
+};
+
+/// Options for providers that need more than an API key.
+///
+/// Provider options are set with
+/// [`ClientBuilder::provider_options`](crate::ClientBuilder::provider_options)
+/// and replace the [`Auth`](crate::Auth) mode: the client sends the provider's
+/// credentials and settings as Portkey headers instead.
+///
+/// # Examples
+///
+/// ```rust
+/// use portkey::{AzureOptions, Client, ProviderOptions};
+///
+/// let azure = AzureOptions::new(
+///     "my-resource",
+///     "gpt-4o-deployment",
+///     "2024-06-01",
+///     "your-azure-api-key",
+/// );
+///
+/// let client = Client::builder()
+///     .api_key("your-portkey-api-key")
+///     .provider_options(ProviderOptions::Azure(azure))
+///     .build()
+///     .expect("valid Portkey configuration");
+/// ```
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum ProviderOptions {
+    /// Azure OpenAI Service.
+    Azure(AzureOptions),
+    /// AWS Bedrock.
+    Bedrock(BedrockOptions),
+    /// Google Vertex AI.
+    Vertex(VertexOptions),
+}
+
+impl ProviderOptions {
+    /// Inserts the provider headers, including `Authorization`.
+    pub(crate) fn apply(&self, map: &mut HeaderMap) -> Result<()> {
+        match self {
+            Self::Azure(azure) => azure.apply(map),
+            Self::Bedrock(bedrock) => bedrock.apply(map),
+            Self::Vertex(vertex) => vertex.apply(map),
+        }
+    }
+
+    /// Returns `true` if the headers change over time and must be applied to
+    /// every request.
+    pub(crate) fn is_dynamic(&self) -> bool {
+        match self {
+            Self::Azure(_) => false,
+            Self::Bedrock(bedrock) => {
+                matches!(bedrock.credentials, BedrockCredentials::Provider(_))
+            }
+            Self::Vertex(vertex) => matches!(vertex.auth, VertexAuth::TokenProvider(_)),
+        }
+    }
+}
+
+/// Options for an Azure OpenAI deployment.
+#[derive(Clone, PartialEq, Eq)]
+pub struct AzureOptions {
+    /// Name of the Azure OpenAI resource.
+    resource_name: String,
+    /// ID of the model deployment.
+    deployment_id: String,
+    /// Azure OpenAI API version, e.g. `2024-06-01`.
+    api_version: String,
+    /// API key of the Azure OpenAI resource.
+    api_key: String,
+    /// Name of the model behind the deployment, used for cost tracking.
+    model_name: Option<String>,
+}
+
+impl fmt::Debug for AzureOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AzureOptions")
+            .field("resource_name", &self.resource_name)
+            .field("deployment_id", &self.deployment_id)
+            .field("api_version", &self.api_version)
+            .field("api_key", &"<redacted>")
+            .field("model_name", &self.model_name)
+            .finish()
+    }
+}
+
+impl AzureOptions {
+    /// Creates options for the deployment `deployment_id` of the Azure
+    /// resource `resource_name`.
+    pub fn new(
+        resource_name: impl Into<String>,
+        deployment_id: impl Into<String>,
+        api_version: impl Into<String>,
+        api_key: impl Into<String>,
+    ) -> Self {
+        Self {
+            resource_name: resource_name.into(),
+            deployment_id: deployment_id.into(),
+            api_version: api_version.into(),
+            api_key: api_key.into(),
+            model_name: None,
+        }
+    }
+
+    /// Sets the name of the model behind the deployment, e.g. `gpt-4o`.
+    pub fn model_name(mut self, model_name: impl Into<String>) -> Self {
+        self.model_name = Some(model_name.into());
+        self
+    }
+
+    fn apply(&self, map: &mut HeaderMap) -> Result<()> {
+        headers::insert(map, headers::PROVIDER, "azure-openai")?;
+        headers::insert(map, headers::AZURE_RESOURCE_NAME, &self.resource_name)?;
+        headers::insert(map, headers::AZURE_DEPLOYMENT_ID, &self.deployment_id)?;
+        headers::insert(map, headers::AZURE_API_VERSION, &self.api_version)?;
+        if let Some(model_name) = &self.model_name {
+            headers::insert(map, headers::AZURE_MODEL_NAME, model_name)?;
+        }
+        headers::insert(
+            map,
+            AUTHORIZATION.as_str(),
+            &format!("Bearer {}", self.api_key),
+        )
+    }
+}
+
+/// Options for AWS Bedrock.
+///
+/// # Examples
+///
+/// ```rust
+/// use portkey::{AwsCredentials, BedrockOptions, Client, CredentialProvider, ProviderOptions};
+///
+/// let credentials = CredentialProvider::new(|| {
+///     // Read credentials kept up to date by your application.
+///     AwsCredentials::new("AKIA...", "secret")
+/// });
+///
+/// let client = Client::builder()
+///     .api_key("your-portkey-api-key")
+///     .provider_options(ProviderOptions::Bedrock(BedrockOptions::with_provider(
+///         "us-east-1",
+///         credentials,
+///     )))
+///     .build()
+///     .expect("valid Portkey configuration");
+/// ```
+#[derive(Debug, Clone)]
+pub struct BedrockOptions {
+    /// AWS region of the Bedrock endpoint.
+    region: String,
+    /// Credentials used to sign requests.
+    credentials: BedrockCredentials,
+}
+
+/// Where [`BedrockOptions`] take their AWS credentials from.
+#[derive(Debug, Clone)]
+enum BedrockCredentials {
+    /// Fixed credentials.
+    Static(AwsCredentials),
+    /// Credentials refreshed before they expire.
+    Provider(CredentialProvider<AwsCredentials>),
+}
+
+impl BedrockOptions {
+    /// Creates options using fixed `credentials`.
+    pub fn new(region: impl Into<String>, credentials: AwsCredentials) -> Self {
+        Self {
+            region: region.into(),
+            credentials: BedrockCredentials::Static(credentials),
+        }
+    }
+
+    /// Creates options taking credentials from `provider`, which is asked for
+    /// new credentials shortly before the current ones expire.
+    pub fn with_provider(
+        region: impl Into<String>,
+        provider: CredentialProvider<AwsCredentials>,
+    ) -> Self {
+        Self {
+            region: region.into(),
+            credentials: BedrockCredentials::Provider(provider),
+        }
+    }
+
+    fn apply(&self, map: &mut HeaderMap) -> Result<()> {
+        let credentials = match &self.credentials {
+            BedrockCredentials::Static(credentials) => credentials.clone(),
+            BedrockCredentials::Provider(provider) => provider.get(),
+        };
+        headers::insert(map, headers::PROVIDER, "bedrock")?;
+        headers::insert(map, headers::AWS_REGION, &self.region)?;
+        headers::insert(map, headers::AWS_ACCESS_KEY_ID, &credentials.access_key_id)?;
+        headers::insert(
+            map,
+            headers::AWS_SECRET_ACCESS_KEY,
+            &credentials.secret_access_key,
+        )?;
+        match &credentials.session_token {
+            Some(session_token) => {
+                headers::insert(map, headers::AWS_SESSION_TOKEN, session_token)?;
+            }
+            None => {
+                map.remove(headers::AWS_SESSION_TOKEN);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Options for Google Vertex AI.
+///
+/// The gateway authenticates against Vertex AI either with a service account
+/// key, from which it derives and refreshes access tokens itself, or with an
+/// access token supplied by the application.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use portkey::{Client, ProviderOptions, VertexOptions};
+///
+/// let vertex = VertexOptions::from_service_account_file("service-account.json", "us-central1")
+///     .expect("valid service account key");
+///
+/// let client = Client::builder()
+///     .api_key("your-portkey-api-key")
+///     .provider_options(ProviderOptions::Vertex(vertex))
+///     .build()
+///     .expect("valid Portkey configuration");
+/// ```
+#[derive(Debug, Clone)]
+pub struct VertexOptions {
+    /// Google Cloud project ID.
+    project_id: String,
+    /// Google Cloud region, e.g. `us-central1`.
+    region: String,
+    /// How requests are authenticated.
+    auth: VertexAuth,
+}
+
+/// How [`VertexOptions`] authenticate against Vertex AI.
+#[derive(Clone)]
+enum VertexAuth {
+    /// Compact JSON of a service account key.
+    ServiceAccount(String),
+    /// A fixed access token.
+    AccessToken(AccessToken),
+    /// Access tokens refreshed before they expire.
+    TokenProvider(CredentialProvider<AccessToken>),
+}
+
+impl fmt::Debug for VertexAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ServiceAccount(_) => f.write_str("ServiceAccount(<redacted>)"),
+            Self::AccessToken(token) => f.debug_tuple("AccessToken").field(token).finish(),
+            Self::TokenProvider(provider) => {
+                f.debug_tuple("TokenProvider").field(provider).finish()
+            }
+        }
+    }
+}
+
+impl VertexOptions {
+    /// Creates options from the contents of a service account key file.
+    ///
+    /// The project ID is taken from the key. The gateway derives access tokens
+    /// from the key and refreshes them as needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Json`] if the key is not valid JSON and
+    /// [`Error::InvalidProviderOptions`] if it has no `project_id`.
+    pub fn from_service_account_json(json: &str, region: impl Into<String>) -> Result<Self> {
+        let key: Value = serde_json::from_str(json)?;
+        let project_id = key
+            .get("project_id")
+            .and_then(Value::as_str)
+            .ok_or_else(|| {
+                Error::InvalidProviderOptions("service account key has no `project_id`".to_string())
+            })?
+            .to_string();
+
+        Ok(Self {
+            project_id,
+            region: region.into(),
+            auth: VertexAuth::ServiceAccount(serde_json::to_string(&key)?),
+        })
+    }
+
+    /// Creates options from a service account key file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the file cannot be read, otherwise see
+    /// [`VertexOptions::from_service_account_json`].
+    pub fn from_service_account_file(
+        path: impl AsRef<Path>,
+        region: impl Into<String>,
+    ) -> Result<Self> {
+        Self::from_service_account_json(&fs::read_to_string(path)?, region)
+    }
+
+    /// Creates options using a fixed access token.
+    pub fn with_access_token(
+        project_id: impl Into<String>,
+        region: impl Into<String>,
+        token: AccessToken,
+    ) -> Self {
+        Self {
+            project_id: project_id.into(),
+            region: region.into(),
+            auth: VertexAuth::AccessToken(token),
+        }
+    }
+
+    /// Creates options taking access tokens from `provider`, which is asked for
+    /// a new token shortly before the current one expires.
+    pub fn with_token_provider(
+        project_id: impl Into<String>,
+        region: impl Into<String>,
+        provider: CredentialProvider<AccessToken>,
+    ) -> Self {
+        Self {
+            project_id: project_id.into(),
+            region: region.into(),
+            auth: VertexAuth::TokenProvider(provider),
+        }
+    }
+
+    fn apply(&self, map: &mut HeaderMap) -> Result<()> {
+        headers::insert(map, headers::PROVIDER, "vertex-ai")?;
+        headers::insert(map, headers::VERTEX_PROJECT_ID, &self.project_id)?;
+        headers::insert(map, headers::VERTEX_REGION, &self.region)?;
+        let token = match &self.auth {
+            VertexAuth::ServiceAccount(json) => {
+                return headers::insert(map, headers::VERTEX_SERVICE_ACCOUNT_JSON, json);
+            }
+            VertexAuth::AccessToken(token) => token.clone(),
+            VertexAuth::TokenProvider(provider) => provider.get(),
+        };
+        headers::insert(
+            map,
+            AUTHORIZATION.as_str(),
+            &format!("Bearer {}", token.token),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::{Duration, SystemTime},
+    };
+
+    use super::*;
+    use crate::{headers::tests::assert_headers, Client};
+
+    /// Returns the headers a client configured with `options` sends.
+    fn client_headers(options: ProviderOptions) -> HeaderMap {
+        let client = Client::builder()
+            .api_key("portkey-key")
+            .provider_options(options)
+            .build()
+            .unwrap();
+        client.config.request_headers()
+    }
+
+    #[test]
+    fn azure_headers() {
+        let azure = AzureOptions::new("resource", "deployment", "2024-06-01", "azure-key")
+            .model_name("gpt-4o");
+
+        assert_headers(
+            &client_headers(ProviderOptions::Azure(azure)),
+            &[
+                ("authorization", "Bearer azure-key"),
+                ("x-portkey-api-key", "portkey-key"),
+                ("x-portkey-provider", "azure-openai"),
+                ("x-portkey-azure-resource-name", "resource"),
+                ("x-portkey-azure-deployment-id", "deployment"),
+                ("x-portkey-azure-api-version", "2024-06-01"),
+                ("x-portkey-azure-model-name", "gpt-4o"),
+            ],
+        );
+    }
+
+    #[test]
+    fn bedrock_headers() {
+        let credentials = AwsCredentials::new("AKIA", "secret").session_token("session");
+        let bedrock = BedrockOptions::new("us-east-1", credentials);
+
+        assert_headers(
+            &client_headers(ProviderOptions::Bedrock(bedrock)),
+            &[
+                ("authorization", "Bearer portkey-key"),
+                ("x-portkey-api-key", "portkey-key"),
+                ("x-portkey-provider", "bedrock"),
+                ("x-portkey-aws-region", "us-east-1"),
+                ("x-portkey-aws-access-key-id", "AKIA"),
+                ("x-portkey-aws-secret-access-key", "secret"),
+                ("x-portkey-aws-session-token", "session"),
+            ],
+        );
+    }
+
+    #[test]
+    fn bedrock_headers_follow_refreshed_credentials() {
+        let fetches = AtomicUsize::new(0);
+        let provider = CredentialProvider::new(move || {
+            if fetches.fetch_add(1, Ordering::SeqCst) == 0 {
+                // Expires within the refresh margin, so the next request
+                // fetches new credentials.
+                AwsCredentials::new("AKIA-OLD", "old-secret")
+                    .session_token("session")
+                    .expires_at(SystemTime::now() + Duration::from_secs(60))
+            } else {
+                AwsCredentials::new("AKIA-NEW", "new-secret")
+            }
+        });
+        let bedrock = BedrockOptions::with_provider("us-east-1", provider);
+
+        assert_headers(
+            &client_headers(ProviderOptions::Bedrock(bedrock)),
+            &[
+                ("authorization", "Bearer portkey-key"),
+                ("x-portkey-api-key", "portkey-key"),
+                ("x-portkey-provider", "bedrock"),
+                ("x-portkey-aws-region", "us-east-1"),
+                ("x-portkey-aws-access-key-id", "AKIA-NEW"),
+                ("x-portkey-aws-secret-access-key", "new-secret"),
+            ],
+        );
+    }
+
+    #[test]
+    fn vertex_service_account_headers() {
+        let key = r#"{ "project_id": "project", "private_key": "secret" }"#;
+        let vertex = VertexOptions::from_service_account_json(key, "us-central1").unwrap();
+
+        assert_headers(
+            &client_headers(ProviderOptions::Vertex(vertex)),
+            &[
+                ("authorization", "Bearer portkey-key"),
+                ("x-portkey-api-key", "portkey-key"),
+                ("x-portkey-provider", "vertex-ai"),
+                ("x-portkey-vertex-project-id", "project"),
+                ("x-portkey-vertex-region", "us-central1"),
+                (
+                    "x-portkey-vertex-service-account-json",
+                    r#"{"private_key":"secret","project_id":"project"}"#,
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn vertex_access_token_headers() {
+        let vertex = VertexOptions::with_access_token(
+            "project",
+            "us-central1",
+            AccessToken::new("ya29.token"),
+        );
+
+        assert_headers(
+            &client_headers(ProviderOptions::Vertex(vertex)),
+            &[
+                ("authorization", "Bearer ya29.token"),
+                ("x-portkey-api-key", "portkey-key"),
+                ("x-portkey-provider", "vertex-ai"),
+                ("x-portkey-vertex-project-id", "project"),
+                ("x-portkey-vertex-region", "us-central1"),
+            ],
+        );
+    }
+
+    #[test]
+    fn vertex_headers_follow_refreshed_tokens() {
+        let fetches = AtomicUsize::new(0);
+        let provider = CredentialProvider::new(move || {
+            let fetch = fetches.fetch_add(1, Ordering::SeqCst) + 1;
+            AccessToken::new(format!("token-{fetch}"))
+                .expires_at(SystemTime::now() + Duration::from_secs(60))
+        });
+        let vertex = VertexOptions::with_token_provider("project", "us-central1", provider);
+        let client = Client::builder()
+            .api_key("portkey-key")
+            .provider_options(ProviderOptions::Vertex(vertex))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            client.config.request_headers()["authorization"],
+            "Bearer token-2"
+        );
+        assert_eq!(
+            client.config.request_headers()["authorization"],
+            "Bearer token-3"
+        );
+    }
+}
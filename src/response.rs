@@ -0,0 +1,280 @@
+//! Responses carrying Portkey's response headers alongside the body.
+
+use std::ops::Deref;
+
+use reqwest::{header::HeaderMap, StatusCode};
+
+use crate::headers;
+
+/// A deserialized response body together with Portkey's response metadata.
+///
+/// `PortkeyResponse` dereferences to the body, so fields of the body can be
+/// accessed directly.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use async_openai::types::CreateEmbeddingRequestArgs;
+/// use portkey::{CacheStatus, Client};
+///
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Client::new("your-portkey-api-key", "your-portkey-virtual-key");
+///
+/// let request = CreateEmbeddingRequestArgs::default()
+///     .model("text-embedding-3-small")
+///     .input("Hello, Portkey!")
+///     .build()?;
+///
+/// let response = client.embeddings().create(request).await?;
+/// println!("{} embeddings", response.data.len());
+///
+/// let meta = response.meta();
+/// if meta.cache_status() == Some(&CacheStatus::Hit) {
+///     println!("served from cache, trace {:?}", meta.trace_id());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PortkeyResponse<T> {
+    /// The deserialized response body.
+    body: T,
+    /// Metadata parsed from the response headers.
+    meta: ResponseMeta,
+}
+
+impl<T> PortkeyResponse<T> {
+    pub(crate) fn new(body: T, meta: ResponseMeta) -> Self {
+        Self { body, meta }
+    }
+
+    /// Returns the response body.
+    pub fn body(&self) -> &T {
+        &self.body
+    }
+
+    /// Returns the metadata parsed from the response headers.
+    pub fn meta(&self) -> &ResponseMeta {
+        &self.meta
+    }
+
+    /// Consumes the response, returning the body.
+    pub fn into_body(self) -> T {
+        self.body
+    }
+
+    /// Consumes the response, returning the body and the metadata.
+    pub fn into_parts(self) -> (T, ResponseMeta) {
+        (self.body, self.meta)
+    }
+}
+
+impl<T> Deref for PortkeyResponse<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.body
+    }
+}
+
+/// Metadata Portkey returns in the headers of a response.
+#[derive(Debug, Clone)]
+pub struct ResponseMeta {
+    /// HTTP status of the response.
+    status: StatusCode,
+    /// Whether the response was served from Portkey's cache.
+    cache_status: Option<CacheStatus>,
+    /// Trace ID of the request.
+    trace_id: Option<String>,
+    /// Number of retries the gateway made.
+    retry_attempt_count: Option<u32>,
+    /// Config target that served the request.
+    last_used_option_index: Option<String>,
+    /// Provider that served the request.
+    provider: Option<String>,
+    /// Number of times the SDK sent the request.
+    attempts: u32,
+    /// All response headers.
+    headers: HeaderMap,
+}
+
+impl ResponseMeta {
+    /// Parses the metadata from the response headers.
+    ///
+    /// `request_trace_id` is used when the gateway does not echo the trace ID.
+    pub(crate) fn from_headers(
+        status: StatusCode,
+        headers: HeaderMap,
+        request_trace_id: Option<&str>,
+    ) -> Self {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_owned)
+        };
+
+        Self {
+            status,
+            cache_status: header(headers::CACHE_STATUS).map(|value| CacheStatus::parse(&value)),
+            trace_id: header(headers::TRACE_ID).or_else(|| request_trace_id.map(str::to_owned)),
+            retry_attempt_count: header(headers::RETRY_ATTEMPT_COUNT)
+                .and_then(|value| value.trim().parse().ok()),
+            last_used_option_index: header(headers::LAST_USED_OPTION_INDEX),
+            provider: header(headers::PROVIDER),
+            attempts: 1,
+            headers,
+        }
+    }
+
+    /// Sets the number of times the SDK sent the request.
+    pub(crate) fn with_attempts(mut self, attempts: u32) -> Self {
+        self.attempts = attempts;
+        self
+    }
+
+    /// Returns the HTTP status of the response.
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// Returns whether the response was served from Portkey's cache, from
+    /// `x-portkey-cache-status`.
+    pub fn cache_status(&self) -> Option<&CacheStatus> {
+        self.cache_status.as_ref()
+    }
+
+    /// Returns the trace ID of the request, from `x-portkey-trace-id`.
+    pub fn trace_id(&self) -> Option<&str> {
+        self.trace_id.as_deref()
+    }
+
+    /// Returns the number of retries the gateway made, from
+    /// `x-portkey-retry-attempt-count`.
+    pub fn retry_attempt_count(&self) -> Option<u32> {
+        self.retry_attempt_count
+    }
+
+    /// Returns the config target that served the request, e.g.
+    /// `config.targets[1]` for the second fallback target, from
+    /// `x-portkey-last-used-option-index`.
+    pub fn last_used_option_index(&self) -> Option<&str> {
+        self.last_used_option_index.as_deref()
+    }
+
+    /// Returns the provider that served the request, from `x-portkey-provider`.
+    pub fn provider(&self) -> Option<&str> {
+        self.provider.as_deref()
+    }
+
+    /// Returns the number of times the SDK sent the request, including
+    /// client-side retries made under a [`RetryPolicy`](crate::RetryPolicy).
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Returns all response headers.
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+}
+
+/// Cache state of a response, from `x-portkey-cache-status`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CacheStatus {
+    /// Served from the simple cache.
+    Hit,
+    /// Served from the semantic cache.
+    SemanticHit,
+    /// Not found in the simple cache.
+    Miss,
+    /// Not found in the semantic cache.
+    SemanticMiss,
+    /// The cached response was refreshed.
+    Refresh,
+    /// Caching is disabled for the request.
+    Disabled,
+    /// A status not known to this SDK.
+    Other(String),
+}
+
+impl CacheStatus {
+    fn parse(value: &str) -> Self {
+        match value.trim().to_ascii_uppercase().replace('_', " ").as_str() {
+            "HIT" => Self::Hit,
+            "SEMANTIC HIT" => Self::SemanticHit,
+            "MISS" => Self::Miss,
+            "SEMANTIC MISS" => Self::SemanticMiss,
+            "REFRESH" => Self::Refresh,
+            "DISABLED" => Self::Disabled,
+            _ => Self::Other(value.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn parses_cache_status() {
+        for (value, status) in [
+            ("HIT", CacheStatus::Hit),
+            ("SEMANTIC HIT", CacheStatus::SemanticHit),
+            ("semantic_hit", CacheStatus::SemanticHit),
+            (" miss ", CacheStatus::Miss),
+            ("SEMANTIC_MISS", CacheStatus::SemanticMiss),
+            ("REFRESH", CacheStatus::Refresh),
+            ("disabled", CacheStatus::Disabled),
+            ("WARM", CacheStatus::Other("WARM".to_string())),
+        ] {
+            assert_eq!(CacheStatus::parse(value), status, "{value}");
+        }
+    }
+
+    #[test]
+    fn parses_response_headers() {
+        let mut headers = HeaderMap::new();
+        for (name, value) in [
+            (headers::CACHE_STATUS, "SEMANTIC HIT"),
+            (headers::TRACE_ID, "response-trace"),
+            (headers::RETRY_ATTEMPT_COUNT, " 2 "),
+            (headers::LAST_USED_OPTION_INDEX, "config.targets[1]"),
+            (headers::PROVIDER, "anthropic"),
+        ] {
+            headers.insert(name, HeaderValue::from_static(value));
+        }
+        let meta = ResponseMeta::from_headers(StatusCode::OK, headers, Some("request-trace"))
+            .with_attempts(3);
+
+        assert_eq!(meta.status(), StatusCode::OK);
+        assert_eq!(meta.cache_status(), Some(&CacheStatus::SemanticHit));
+        assert_eq!(meta.trace_id(), Some("response-trace"));
+        assert_eq!(meta.retry_attempt_count(), Some(2));
+        assert_eq!(meta.last_used_option_index(), Some("config.targets[1]"));
+        assert_eq!(meta.provider(), Some("anthropic"));
+        assert_eq!(meta.attempts(), 3);
+    }
+
+    #[test]
+    fn falls_back_to_request_trace_id() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            headers::RETRY_ATTEMPT_COUNT,
+            HeaderValue::from_static("unknown"),
+        );
+        let meta = ResponseMeta::from_headers(StatusCode::OK, headers, Some("request-trace"));
+
+        assert_eq!(meta.trace_id(), Some("request-trace"));
+        assert_eq!(meta.retry_attempt_count(), None);
+        assert_eq!(meta.cache_status(), None);
+        assert_eq!(meta.attempts(), 1);
+        assert_eq!(
+            ResponseMeta::from_headers(StatusCode::OK, HeaderMap::new(), None).trace_id(),
+            None
+        );
+    }
+}
@@ -0,0 +1,301 @@
+//! Client-side retries of requests that did not reach the Portkey gateway.
+
+use std::time::Duration;
+
+use rand::Rng;
+use reqwest::{header::RETRY_AFTER, Method, Response, StatusCode};
+
+use crate::Error;
+
+/// Client-side retry policy for requests sent by the SDK.
+///
+/// The gateway's own retries, configured with
+/// [`RetryConfig`](crate::config::RetryConfig), only apply once a request has
+/// reached the gateway. This policy covers failures on the way there, such as
+/// DNS, TLS or connection errors. Only failures that are safe to retry are
+/// retried:
+///
+/// - connection errors, where the request was never sent,
+/// - any transport error of an idempotent request, e.g. a timed out `GET`,
+/// - `429` and `503` responses carrying a `Retry-After` header, after the
+///   delay requested by the gateway. If that delay exceeds the maximum
+///   backoff, the response is returned instead.
+///
+/// Retries are delayed with jittered exponential backoff. The policy applies to
+/// calls made through the SDK, e.g. [`Client::chat`](crate::Client::chat), but
+/// not to the [`async_openai`] client returned by
+/// [`Client::openai`](crate::Client::openai).
+///
+/// The number of attempts is reported by
+/// [`ResponseMeta::attempts`](crate::ResponseMeta::attempts) on success, and by
+/// [`ApiError::attempts`](crate::ApiError::attempts) and
+/// [`Error::RetriesExhausted`](crate::Error::RetriesExhausted) on failure.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use portkey::{Client, RetryPolicy};
+///
+/// let client = Client::builder()
+///     .api_key("your-portkey-api-key")
+///     .virtual_key("your-portkey-virtual-key")
+///     .retry_policy(RetryPolicy::new(3).initial_backoff(Duration::from_millis(100)))
+///     .build()
+///     .expect("valid Portkey configuration");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Maximum number of retries after the first attempt.
+    max_retries: u32,
+    /// Upper bound of the delay before the first retry.
+    initial_backoff: Duration,
+    /// Upper bound of any delay. Longer `Retry-After` delays are not waited
+    /// for.
+    max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Creates a policy retrying a request up to `max_retries` times.
+    ///
+    /// The backoff starts at 200 milliseconds and is capped at 10 seconds.
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(10),
+        }
+    }
+
+    /// Sets the upper bound of the delay before the first retry. The bound
+    /// doubles with every further retry.
+    pub fn initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Sets the upper bound of any delay. Responses requesting a longer delay
+    /// with `Retry-After` are not retried.
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Returns the delay before retry number `retry` (starting at 1), or `None`
+    /// if the outcome of the previous attempt must not be retried.
+    pub(crate) fn retry_delay(
+        &self,
+        method: &Method,
+        outcome: &Result<Response, Error>,
+        retry: u32,
+    ) -> Option<Duration> {
+        if retry > self.max_retries {
+            return None;
+        }
+        self.delay(method, outcome, retry)
+    }
+
+    /// Returns `true` if the outcome would be retried, but retry number
+    /// `retry` exceeds the maximum number of retries.
+    pub(crate) fn is_exhausted(
+        &self,
+        method: &Method,
+        outcome: &Result<Response, Error>,
+        retry: u32,
+    ) -> bool {
+        retry > self.max_retries && self.delay(method, outcome, retry).is_some()
+    }
+
+    /// Returns the delay before retry number `retry` if the outcome is safe
+    /// to retry, regardless of the maximum number of retries.
+    fn delay(
+        &self,
+        method: &Method,
+        outcome: &Result<Response, Error>,
+        retry: u32,
+    ) -> Option<Duration> {
+        match outcome {
+            Err(Error::Request(error)) if error.is_connect() || method.is_idempotent() => {
+                Some(self.backoff(retry))
+            }
+            Err(_) => None,
+            Ok(response) => matches!(
+                response.status(),
+                StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE
+            )
+            .then(|| retry_after(response))
+            .flatten()
+            .filter(|delay| *delay <= self.max_backoff),
+        }
+    }
+
+    /// Returns a random delay between zero and the exponential bound of
+    /// retry number `retry`.
+    fn backoff(&self, retry: u32) -> Duration {
+        let bound = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+            .min(self.max_backoff);
+        bound.mul_f64(rand::thread_rng().gen_range(0.0..=1.0))
+    }
+}
+
+/// Parses a `Retry-After` header given in seconds.
+fn retry_after(response: &Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(status: StatusCode, retry_after: Option<&str>) -> Result<Response, Error> {
+        let mut response = http::Response::builder().status(status);
+        if let Some(retry_after) = retry_after {
+            response = response.header(RETRY_AFTER, retry_after);
+        }
+        Ok(Response::from(response.body("").unwrap()))
+    }
+
+    /// Returns the error of a request to a closed port.
+    async fn connect_error() -> Error {
+        let error = reqwest::get("http://127.0.0.1:1").await.unwrap_err();
+        assert!(error.is_connect());
+        Error::Request(error)
+    }
+
+    /// Returns a transport error that is not a connection error.
+    fn builder_error() -> Error {
+        let error = reqwest::Client::new().get("not a url").build().unwrap_err();
+        assert!(!error.is_connect());
+        Error::Request(error)
+    }
+
+    #[test]
+    fn backoff_is_bounded_exponentially() {
+        let policy = RetryPolicy::new(10)
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_millis(1000));
+
+        for (retry, bound) in [(1, 100), (2, 200), (3, 400), (4, 800), (5, 1000), (9, 1000)] {
+            for _ in 0..100 {
+                assert!(policy.backoff(retry) <= Duration::from_millis(bound));
+            }
+        }
+        // The bound saturates instead of overflowing.
+        assert!(RetryPolicy::new(1).backoff(u32::MAX) <= Duration::from_secs(10));
+    }
+
+    #[tokio::test]
+    async fn retries_connect_errors_up_to_max_retries() {
+        let policy = RetryPolicy::new(2);
+        let outcome = Err(connect_error().await);
+
+        assert!(policy.retry_delay(&Method::POST, &outcome, 1).is_some());
+        assert!(policy.retry_delay(&Method::POST, &outcome, 2).is_some());
+        assert_eq!(policy.retry_delay(&Method::POST, &outcome, 3), None);
+        assert_eq!(
+            RetryPolicy::new(0).retry_delay(&Method::GET, &outcome, 1),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn is_exhausted_only_for_retryable_outcomes() {
+        let policy = RetryPolicy::new(1);
+        let connect = Err(connect_error().await);
+        let builder = Err(builder_error());
+
+        assert!(!policy.is_exhausted(&Method::POST, &connect, 1));
+        assert!(policy.is_exhausted(&Method::POST, &connect, 2));
+        assert!(policy.is_exhausted(&Method::GET, &builder, 2));
+        // A failed `POST` that is not a connection error is never retried, so
+        // it does not exhaust the retries.
+        assert!(!policy.is_exhausted(&Method::POST, &builder, 2));
+    }
+
+    #[test]
+    fn retries_other_transport_errors_of_idempotent_requests_only() {
+        let policy = RetryPolicy::new(3);
+        let outcome = Err(builder_error());
+
+        assert!(policy.retry_delay(&Method::GET, &outcome, 1).is_some());
+        assert!(policy.retry_delay(&Method::PUT, &outcome, 1).is_some());
+        assert_eq!(policy.retry_delay(&Method::POST, &outcome, 1), None);
+        assert_eq!(
+            policy.retry_delay(&Method::GET, &Err(Error::Stream(String::new())), 1),
+            None
+        );
+    }
+
+    #[test]
+    fn retries_responses_with_retry_after() {
+        let policy = RetryPolicy::new(3).max_backoff(Duration::from_secs(5));
+
+        for status in [
+            StatusCode::TOO_MANY_REQUESTS,
+            StatusCode::SERVICE_UNAVAILABLE,
+        ] {
+            assert_eq!(
+                policy.retry_delay(&Method::POST, &response(status, Some("2")), 1),
+                Some(Duration::from_secs(2))
+            );
+            assert_eq!(
+                policy.retry_delay(&Method::POST, &response(status, Some("5")), 1),
+                Some(Duration::from_secs(5))
+            );
+            assert_eq!(
+                policy.retry_delay(&Method::POST, &response(status, None), 1),
+                None
+            );
+            assert_eq!(
+                policy.retry_delay(&Method::POST, &response(status, Some("soon")), 1),
+                None
+            );
+        }
+        assert_eq!(
+            policy.retry_delay(
+                &Method::GET,
+                &response(StatusCode::INTERNAL_SERVER_ERROR, Some("1")),
+                1
+            ),
+            None
+        );
+        assert_eq!(
+            policy.retry_delay(&Method::GET, &response(StatusCode::OK, None), 1),
+            None
+        );
+    }
+
+    #[test]
+    fn does_not_wait_for_retry_after_above_max_backoff() {
+        let policy = RetryPolicy::new(3).max_backoff(Duration::from_secs(5));
+
+        assert_eq!(
+            policy.retry_delay(
+                &Method::POST,
+                &response(StatusCode::TOO_MANY_REQUESTS, Some("6")),
+                1
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn does_not_retry_responses_after_max_retries() {
+        let policy = RetryPolicy::new(1);
+        let outcome = response(StatusCode::SERVICE_UNAVAILABLE, Some("1"));
+
+        assert!(policy.retry_delay(&Method::POST, &outcome, 1).is_some());
+        assert_eq!(policy.retry_delay(&Method::POST, &outcome, 2), None);
+    }
+}